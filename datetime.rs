@@ -0,0 +1,370 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Calendar helpers for the date and time types. Dates count days from
+// 1970-01-01, times and timestamps count microseconds, and time zones are
+// fixed UTC offsets in seconds so no time zone database is needed.
+
+pub const MICROS_PER_SECOND: i64 = 1_000_000;
+pub const MICROS_PER_MINUTE: i64 = 60 * MICROS_PER_SECOND;
+pub const MICROS_PER_HOUR: i64 = 60 * MICROS_PER_MINUTE;
+pub const MICROS_PER_DAY: i64 = 24 * MICROS_PER_HOUR;
+// Dates stay within the days a timestamp can hold, so any date and time
+// of day converts to a timestamp without overflowing
+pub const MAX_DATE_DAYS: i64 = i64::MAX / MICROS_PER_DAY - 1;
+
+// Months and days are kept apart from the time part because their length
+// depends on the date they are added to
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Interval {
+    pub months: i32,
+    pub days: i32,
+    pub micros: i64
+}
+
+impl Interval {
+    // Approximate length used for ordering, counting a month as 30 days
+    pub fn total_micros(&self) -> i128 {
+        return (self.months as i128 * 30 + self.days as i128) * MICROS_PER_DAY as i128 + self.micros as i128;
+    }
+}
+
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    return era * 146097 + day_of_era - 719468;
+}
+
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = if days >= 0 { days } else { days - 146096 } / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    return (year, month, day);
+}
+
+pub fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 => if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 { 29 } else { 28 },
+        4 | 6 | 9 | 11 => 30,
+        _ => 31
+    }
+}
+
+// Adds months to a date, clamping the day to the end of the target month
+pub fn add_months(days: i64, months: i64) -> i64 {
+    let (year, month, day) = civil_from_days(days);
+    let index = year * 12 + month as i64 - 1 + months;
+    let year = index.div_euclid(12);
+    let month = index.rem_euclid(12) as u32 + 1;
+    return days_from_civil(year, month, day.min(days_in_month(year, month)));
+}
+
+pub fn add_interval(micros: i64, interval: &Interval) -> Option<i64> {
+    let days = micros.div_euclid(MICROS_PER_DAY);
+    let time = micros.rem_euclid(MICROS_PER_DAY);
+    let days = add_months(days, interval.months as i64) + interval.days as i64;
+    return days.checked_mul(MICROS_PER_DAY)?.checked_add(time)?.checked_add(interval.micros);
+}
+
+pub fn now_micros() -> i64 {
+    let elapsed = SystemTime::now().duration_since(UNIX_EPOCH).expect("System clock is before 1970");
+    return elapsed.as_micros() as i64;
+}
+
+fn parse_digits(text: &str, length: usize) -> Option<u32> {
+    if text.len() != length || !text.chars().all(|character| character.is_ascii_digit()) {
+        return None;
+    }
+    return text.parse().ok();
+}
+
+// Parses YYYY-MM-DD
+pub fn parse_date(text: &str) -> Option<i32> {
+    let parts: Vec<&str> = text.split('-').collect();
+    if parts.len() != 3 {
+        return None;
+    }
+    let year = parse_digits(parts[0], 4)? as i64;
+    let month = parse_digits(parts[1], 2)?;
+    let day = parse_digits(parts[2], 2)?;
+    if month < 1 || month > 12 || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+    return Some(days_from_civil(year, month, day) as i32);
+}
+
+// Parses HH:MM[:SS[.ffffff]]
+pub fn parse_time(text: &str) -> Option<i64> {
+    let parts: Vec<&str> = text.split(':').collect();
+    if parts.len() < 2 || parts.len() > 3 {
+        return None;
+    }
+    let hour = parse_digits(parts[0], 2)? as i64;
+    let minute = parse_digits(parts[1], 2)? as i64;
+    let mut micros = 0;
+    if parts.len() == 3 {
+        let (seconds, fraction) = match parts[2].split_once('.') {
+            Some((seconds, fraction)) => (seconds, fraction),
+            None => (parts[2], "")
+        };
+        micros = parse_digits(seconds, 2)? as i64 * MICROS_PER_SECOND;
+        if !fraction.is_empty() {
+            if fraction.len() > 6 {
+                return None;
+            }
+            micros += parse_digits(fraction, fraction.len())? as i64 * 10i64.pow(6 - fraction.len() as u32);
+        }
+    }
+    if hour > 24 || minute > 59 || micros >= 60 * MICROS_PER_SECOND || (hour == 24 && (minute > 0 || micros > 0)) {
+        return None;
+    }
+    return Some(hour * MICROS_PER_HOUR + minute * MICROS_PER_MINUTE + micros);
+}
+
+// Parses Z, +HH, +HH:MM or +HHMM into an offset in seconds
+pub fn parse_offset(text: &str) -> Option<i32> {
+    if text == "Z" {
+        return Some(0);
+    }
+    let sign = match text.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None
+    };
+    let digits = text[1..].replace(':', "");
+    let (hours, minutes) = match digits.len() {
+        2 => (parse_digits(&digits, 2)?, 0),
+        4 => (parse_digits(&digits[0..2], 2)?, parse_digits(&digits[2..4], 2)?),
+        _ => return None
+    };
+    if hours > 15 || minutes > 59 {
+        return None;
+    }
+    return Some(sign * (hours * 3600 + minutes * 60) as i32);
+}
+
+// Parses an ISO-8601 date and optional time separated by 'T' or a space,
+// with an optional UTC offset. Returns the local wall clock time and the
+// offset if one was given.
+pub fn parse_timestamp(text: &str) -> Option<(i64, Option<i32>)> {
+    let text = text.trim();
+    let (date, rest) = match text.find(|character| character == 'T' || character == ' ') {
+        Some(index) => (&text[..index], text[index + 1..].trim()),
+        None => (text, "")
+    };
+    let days = parse_date(date)? as i64;
+    if rest.is_empty() {
+        return Some((days * MICROS_PER_DAY, None));
+    }
+    let (time, offset) = match rest.find(|character| character == 'Z' || character == '+' || character == '-') {
+        Some(index) => (rest[..index].trim(), Some(parse_offset(rest[index..].trim())?)),
+        None => (rest, None)
+    };
+    return Some((days * MICROS_PER_DAY + parse_time(time)?, offset));
+}
+
+// Parses either an ISO-8601 duration such as P1Y2M3DT4H5M6.5S or a list of
+// quantities and units such as '1 year 2 months 3 days 04:05:06'
+// Adds amounts read from an interval, failing when a part no longer fits
+// instead of saturating
+fn add_parts(interval: &mut Interval, months: f64, days: f64, micros: f64) -> Option<()> {
+    // The bound is the power of two just past the largest value
+    let fits = |value: f64, bound: f64| value.is_finite() && value.trunc() >= -bound && value.trunc() < bound;
+    if !fits(months, 2f64.powi(31)) || !fits(days, 2f64.powi(31)) || !fits(micros, 2f64.powi(63)) {
+        return None;
+    }
+    interval.months = interval.months.checked_add(months as i32)?;
+    interval.days = interval.days.checked_add(days as i32)?;
+    interval.micros = interval.micros.checked_add(micros as i64)?;
+    return Some(());
+}
+
+pub fn parse_interval(text: &str) -> Option<Interval> {
+    let text = text.trim();
+    let mut interval = Interval { months: 0, days: 0, micros: 0 };
+    if let Some(duration) = text.strip_prefix('P') {
+        let mut in_time = false;
+        let mut number = String::new();
+        for character in duration.chars() {
+            if character == 'T' {
+                in_time = true;
+                continue;
+            }
+            if character.is_ascii_digit() || character == '.' || character == '-' {
+                number.push(character);
+                continue;
+            }
+            let value: f64 = number.parse().ok()?;
+            number.clear();
+            match (in_time, character) {
+                (false, 'Y') => add_parts(&mut interval, value * 12.0, 0.0, 0.0)?,
+                (false, 'M') => add_parts(&mut interval, value, 0.0, 0.0)?,
+                (false, 'W') => add_parts(&mut interval, 0.0, value * 7.0, 0.0)?,
+                (false, 'D') => add_parts(&mut interval, 0.0, value, 0.0)?,
+                (true, 'H') => add_parts(&mut interval, 0.0, 0.0, value * MICROS_PER_HOUR as f64)?,
+                (true, 'M') => add_parts(&mut interval, 0.0, 0.0, value * MICROS_PER_MINUTE as f64)?,
+                (true, 'S') => add_parts(&mut interval, 0.0, 0.0, (value * MICROS_PER_SECOND as f64).round())?,
+                _ => return None
+            }
+        }
+        if !number.is_empty() {
+            return None;
+        }
+        return Some(interval);
+    }
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.is_empty() {
+        return None;
+    }
+    let mut i = 0;
+    while i < words.len() {
+        if words[i].contains(':') {
+            let (sign, time) = match words[i].strip_prefix('-') {
+                Some(time) => (-1, time),
+                None => (1, words[i])
+            };
+            interval.micros = interval.micros.checked_add(sign * parse_time(time)?)?;
+            i += 1;
+            continue;
+        }
+        let value: f64 = words[i].parse().ok()?;
+        let unit = words.get(i + 1)?.to_lowercase();
+        match unit.trim_end_matches('s') {
+            "year" => add_parts(&mut interval, value * 12.0, 0.0, 0.0)?,
+            "mon" | "month" => add_parts(&mut interval, value, 0.0, 0.0)?,
+            "week" => add_parts(&mut interval, 0.0, value * 7.0, 0.0)?,
+            "day" => add_parts(&mut interval, 0.0, value, 0.0)?,
+            "hour" => add_parts(&mut interval, 0.0, 0.0, value * MICROS_PER_HOUR as f64)?,
+            "min" | "minute" => add_parts(&mut interval, 0.0, 0.0, value * MICROS_PER_MINUTE as f64)?,
+            "sec" | "second" => add_parts(&mut interval, 0.0, 0.0, (value * MICROS_PER_SECOND as f64).round())?,
+            _ => return None
+        }
+        i += 2;
+    }
+    return Some(interval);
+}
+
+pub fn format_date(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    return format!("{:04}-{:02}-{:02}", year, month, day);
+}
+
+pub fn format_time(micros: i64) -> String {
+    let seconds = micros / MICROS_PER_SECOND;
+    let fraction = micros % MICROS_PER_SECOND;
+    let mut text = format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if fraction != 0 {
+        text.push_str(format!(".{:06}", fraction).trim_end_matches('0'));
+    }
+    return text;
+}
+
+pub fn format_timestamp(micros: i64) -> String {
+    let days = micros.div_euclid(MICROS_PER_DAY);
+    return format!("{} {}", format_date(days), format_time(micros.rem_euclid(MICROS_PER_DAY)));
+}
+
+pub fn format_offset(offset: i32) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.abs();
+    return format!("{}{:02}:{:02}", sign, offset / 3600, offset / 60 % 60);
+}
+
+pub fn format_interval(interval: &Interval) -> String {
+    let mut parts = Vec::new();
+    let years = interval.months / 12;
+    let months = interval.months % 12;
+    if years != 0 {
+        parts.push(format!("{} year{}", years, if years.abs() == 1 { "" } else { "s" }));
+    }
+    if months != 0 {
+        parts.push(format!("{} mon{}", months, if months.abs() == 1 { "" } else { "s" }));
+    }
+    if interval.days != 0 {
+        parts.push(format!("{} day{}", interval.days, if interval.days.abs() == 1 { "" } else { "s" }));
+    }
+    if interval.micros != 0 || parts.is_empty() {
+        let sign = if interval.micros < 0 { "-" } else { "" };
+        parts.push(format!("{}{}", sign, format_time(interval.micros.abs())));
+    }
+    return parts.join(" ");
+}
+
+// Truncates a wall clock time to the start of the given field
+pub fn truncate(field: &str, micros: i64) -> Option<i64> {
+    let days = micros.div_euclid(MICROS_PER_DAY);
+    let time = micros.rem_euclid(MICROS_PER_DAY);
+    let (year, month, _) = civil_from_days(days);
+    let start_of_day = match field {
+        "YEAR" => days_from_civil(year, 1, 1),
+        "QUARTER" => days_from_civil(year, (month - 1) / 3 * 3 + 1, 1),
+        "MONTH" => days_from_civil(year, month, 1),
+        // Weeks start on Monday; 1970-01-01 was a Thursday
+        "WEEK" => days - (days + 3).rem_euclid(7),
+        "DAY" => days,
+        _ => {
+            let unit = match field {
+                "HOUR" => MICROS_PER_HOUR,
+                "MINUTE" => MICROS_PER_MINUTE,
+                "SECOND" => MICROS_PER_SECOND,
+                _ => return None
+            };
+            return Some(days * MICROS_PER_DAY + time - time % unit);
+        }
+    };
+    return Some(start_of_day * MICROS_PER_DAY);
+}
+
+// Extracts an integer field from a wall clock time. SECOND and EPOCH are
+// handled by the caller because they have a fractional part.
+pub fn extract(field: &str, micros: i64) -> Option<i64> {
+    let days = micros.div_euclid(MICROS_PER_DAY);
+    let time = micros.rem_euclid(MICROS_PER_DAY);
+    let (year, month, day) = civil_from_days(days);
+    let value = match field {
+        "YEAR" => year,
+        "QUARTER" => (month as i64 - 1) / 3 + 1,
+        "MONTH" => month as i64,
+        "DAY" => day as i64,
+        // Sunday is 0
+        "DOW" => (days + 4).rem_euclid(7),
+        "DOY" => days - days_from_civil(year, 1, 1) + 1,
+        "WEEK" => {
+            // ISO week: the week containing the year's first Thursday is week 1
+            let thursday = days - (days + 3).rem_euclid(7) + 3;
+            let (iso_year, _, _) = civil_from_days(thursday);
+            (thursday - days_from_civil(iso_year, 1, 1)) / 7 + 1
+        },
+        "HOUR" => time / MICROS_PER_HOUR,
+        "MINUTE" => time / MICROS_PER_MINUTE % 60,
+        _ => return None
+    };
+    return Some(value);
+}
+
+pub fn negate_interval(interval: &Interval) -> Interval {
+    return Interval { months: -interval.months, days: -interval.days, micros: -interval.micros };
+}
+
+// Multiplies an interval, cascading fractional months into days and
+// fractional days into the time part
+pub fn scale_interval(interval: &Interval, factor: f64) -> Interval {
+    let months = interval.months as f64 * factor;
+    let days = interval.days as f64 * factor + months.fract() * 30.0;
+    let micros = interval.micros as f64 * factor + days.fract() * MICROS_PER_DAY as f64;
+    return Interval { months: months.trunc() as i32, days: days.trunc() as i32, micros: micros.round() as i64 };
+}
+
+// Expresses a difference between two timestamps as days and time
+pub fn interval_between(micros: i64) -> Interval {
+    return Interval { months: 0, days: (micros / MICROS_PER_DAY) as i32, micros: micros % MICROS_PER_DAY };
+}
@@ -0,0 +1,66 @@
+// Helpers for exact decimals stored as an unscaled i128 and a scale, so that
+// 12.34 is held as (1234, 2)
+
+pub const MAX_PRECISION: u32 = 38;
+
+pub fn parse_decimal(text: &str) -> Option<(i128, u32)> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text))
+    };
+    let (integer_part, fraction_part) = match digits.split_once('.') {
+        Some((integer_part, fraction_part)) => (integer_part, fraction_part),
+        None => (digits, "")
+    };
+    if integer_part.is_empty() && fraction_part.is_empty() {
+        return None;
+    }
+    let mut value: i128 = 0;
+    for character in integer_part.chars().chain(fraction_part.chars()) {
+        let digit = character.to_digit(10)? as i128;
+        value = value.checked_mul(10)?.checked_add(digit)?;
+    }
+    if negative {
+        value = -value;
+    }
+    return Some((value, fraction_part.len() as u32));
+}
+
+pub fn format_decimal(value: i128, scale: u32) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let sign = if value < 0 { "-" } else { "" };
+    if scale == 0 {
+        return format!("{}{}", sign, digits);
+    }
+    let digits = format!("{:0>width$}", digits, width = scale as usize + 1);
+    let (integer_part, fraction_part) = digits.split_at(digits.len() - scale as usize);
+    return format!("{}{}.{}", sign, integer_part, fraction_part);
+}
+
+// Changes the scale of a value, rounding half away from zero when digits
+// are dropped. Returns None on overflow. Dropping more digits than an i128
+// holds leaves 0.
+pub fn rescale(value: i128, scale: u32, new_scale: u32) -> Option<i128> {
+    if new_scale >= scale {
+        return value.checked_mul(10i128.checked_pow(new_scale - scale)?);
+    }
+    let divisor = match 10i128.checked_pow(scale - new_scale) {
+        Some(divisor) => divisor,
+        None => return Some(0)
+    };
+    let quotient = value / divisor;
+    let remainder = value % divisor;
+    if remainder.unsigned_abs() * 2 >= divisor.unsigned_abs() {
+        return Some(quotient + value.signum());
+    }
+    return Some(quotient);
+}
+
+// Number of significant digits, as counted against a column's precision
+pub fn precision_of(value: i128) -> u32 {
+    return value.unsigned_abs().to_string().len() as u32;
+}
+
+pub fn to_f64(value: i128, scale: u32) -> f64 {
+    return value as f64 / 10f64.powi(scale as i32);
+}
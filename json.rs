@@ -0,0 +1,362 @@
+// JSON documents are validated once on insert and kept in parsed form. Object
+// members keep the order they were written in.
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum JsonValue {
+    Null,
+    Boolean(bool),
+    // Numbers keep their original text so no precision is lost
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>)
+}
+
+// A single step of a path such as $.items[0].name
+#[derive(PartialEq, Debug, Clone)]
+pub enum PathStep {
+    Key(String),
+    Index(i64)
+}
+
+impl JsonValue {
+    pub fn type_name(&self) -> &str {
+        match self {
+            JsonValue::Null => "null",
+            JsonValue::Boolean(_) => "boolean",
+            JsonValue::Number(_) => "number",
+            JsonValue::String(_) => "string",
+            JsonValue::Array(_) => "array",
+            JsonValue::Object(_) => "object"
+        }
+    }
+
+    pub fn get_key(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(members) => members.iter().rev().find(|(name, _)| name == key).map(|(_, value)| value),
+            _ => None
+        }
+    }
+
+    // Negative indexes count from the end of the array
+    pub fn get_index(&self, index: i64) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Array(elements) => {
+                let index = if index < 0 { elements.len() as i64 + index } else { index };
+                if index < 0 {
+                    return None;
+                }
+                elements.get(index as usize)
+            },
+            _ => None
+        }
+    }
+
+    pub fn get_path(&self, path: &Vec<PathStep>) -> Option<&JsonValue> {
+        let mut value = self;
+        for step in path {
+            value = match step {
+                PathStep::Key(key) => value.get_key(key)?,
+                PathStep::Index(index) => value.get_index(*index)?
+            };
+        }
+        return Some(value);
+    }
+}
+
+// Values are parsed recursively, so nesting is limited to keep a document
+// from overflowing the stack
+const MAX_DEPTH: usize = 1000;
+
+struct JsonParser {
+    characters: Vec<char>,
+    cursor: usize,
+    depth: usize
+}
+
+impl JsonParser {
+    fn peek(&self) -> Option<char> {
+        return self.characters.get(self.cursor).cloned();
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(character) = self.peek() {
+            if character != ' ' && character != '\t' && character != '\n' && character != '\r' {
+                break;
+            }
+            self.cursor += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.peek() {
+            Some(character) if character == expected => {
+                self.cursor += 1;
+                Ok(())
+            },
+            Some(character) => Err(format!("expected '{}' but found '{}' at position {}", expected, character, self.cursor)),
+            None => Err(format!("expected '{}' but the document ended", expected))
+        }
+    }
+
+    fn expect_word(&mut self, word: &str, value: JsonValue) -> Result<JsonValue, String> {
+        for expected in word.chars() {
+            self.expect(expected)?;
+        }
+        return Ok(value);
+    }
+
+    fn parse_value(&mut self) -> Result<JsonValue, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') | Some('[') => {
+                if self.depth == MAX_DEPTH {
+                    return Err(format!("nesting deeper than {} levels at position {}", MAX_DEPTH, self.cursor));
+                }
+                self.depth += 1;
+                let value = if self.peek() == Some('{') { self.parse_object() } else { self.parse_array() };
+                self.depth -= 1;
+                value
+            },
+            Some('"') => Ok(JsonValue::String(self.parse_string()?)),
+            Some('t') => self.expect_word("true", JsonValue::Boolean(true)),
+            Some('f') => self.expect_word("false", JsonValue::Boolean(false)),
+            Some('n') => self.expect_word("null", JsonValue::Null),
+            Some(character) if character == '-' || character.is_ascii_digit() => self.parse_number(),
+            Some(character) => Err(format!("unexpected character '{}' at position {}", character, self.cursor)),
+            None => Err("the document ended unexpectedly".to_string())
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<JsonValue, String> {
+        self.expect('{')?;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.cursor += 1;
+            return Ok(JsonValue::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            let value = self.parse_value()?;
+            members.push((key, value));
+            self.skip_whitespace();
+            if self.peek() == Some(',') {
+                self.cursor += 1;
+                continue;
+            }
+            self.expect('}')?;
+            return Ok(JsonValue::Object(members));
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<JsonValue, String> {
+        self.expect('[')?;
+        let mut elements = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.cursor += 1;
+            return Ok(JsonValue::Array(elements));
+        }
+        loop {
+            elements.push(self.parse_value()?);
+            self.skip_whitespace();
+            if self.peek() == Some(',') {
+                self.cursor += 1;
+                continue;
+            }
+            self.expect(']')?;
+            return Ok(JsonValue::Array(elements));
+        }
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, String> {
+        let mut value = 0;
+        for _ in 0..4 {
+            let digit = self.peek().and_then(|character| character.to_digit(16));
+            match digit {
+                Some(digit) => value = value * 16 + digit,
+                None => return Err(format!("invalid unicode escape at position {}", self.cursor))
+            }
+            self.cursor += 1;
+        }
+        return Ok(value);
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut text = String::new();
+        loop {
+            let character = match self.peek() {
+                Some(character) => character,
+                None => return Err("unterminated string".to_string())
+            };
+            self.cursor += 1;
+            match character {
+                '"' => return Ok(text),
+                '\\' => {
+                    let escape = self.peek().ok_or("unterminated string".to_string())?;
+                    self.cursor += 1;
+                    match escape {
+                        '"' => text.push('"'),
+                        '\\' => text.push('\\'),
+                        '/' => text.push('/'),
+                        'b' => text.push('\u{8}'),
+                        'f' => text.push('\u{c}'),
+                        'n' => text.push('\n'),
+                        'r' => text.push('\r'),
+                        't' => text.push('\t'),
+                        'u' => {
+                            let mut code = self.parse_hex4()?;
+                            // Characters outside the basic plane are written
+                            // as a surrogate pair
+                            if (0xD800..0xDC00).contains(&code) && self.peek() == Some('\\') {
+                                self.cursor += 1;
+                                self.expect('u')?;
+                                let low = self.parse_hex4()?;
+                                code = 0x10000 + ((code - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF);
+                            }
+                            match char::from_u32(code) {
+                                Some(character) => text.push(character),
+                                None => return Err(format!("invalid unicode escape at position {}", self.cursor))
+                            }
+                        },
+                        _ => return Err(format!("invalid escape '\\{}' at position {}", escape, self.cursor - 1))
+                    }
+                },
+                _ if (character as u32) < 0x20 => return Err(format!("control character in string at position {}", self.cursor - 1)),
+                _ => text.push(character)
+            }
+        }
+    }
+
+    fn parse_digits(&mut self, text: &mut String) -> usize {
+        let mut count = 0;
+        while let Some(character) = self.peek() {
+            if !character.is_ascii_digit() {
+                break;
+            }
+            text.push(character);
+            self.cursor += 1;
+            count += 1;
+        }
+        return count;
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, String> {
+        let start = self.cursor;
+        let invalid = format!("invalid number at position {}", start);
+        let mut text = String::new();
+        if self.peek() == Some('-') {
+            text.push('-');
+            self.cursor += 1;
+        }
+        let leading_zero = self.peek() == Some('0');
+        let digits = self.parse_digits(&mut text);
+        if digits == 0 || (leading_zero && digits > 1) {
+            return Err(invalid);
+        }
+        if self.peek() == Some('.') {
+            text.push('.');
+            self.cursor += 1;
+            if self.parse_digits(&mut text) == 0 {
+                return Err(invalid);
+            }
+        }
+        if self.peek() == Some('e') || self.peek() == Some('E') {
+            text.push('e');
+            self.cursor += 1;
+            if let Some(sign) = self.peek().filter(|character| *character == '+' || *character == '-') {
+                text.push(sign);
+                self.cursor += 1;
+            }
+            if self.parse_digits(&mut text) == 0 {
+                return Err(invalid);
+            }
+        }
+        return Ok(JsonValue::Number(text));
+    }
+}
+
+pub fn parse_json(text: &str) -> Result<JsonValue, String> {
+    let mut parser = JsonParser { characters: text.chars().collect(), cursor: 0, depth: 0 };
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+    if parser.cursor < parser.characters.len() {
+        return Err(format!("unexpected character '{}' at position {}", parser.characters[parser.cursor], parser.cursor));
+    }
+    return Ok(value);
+}
+
+fn format_string(text: &str) -> String {
+    let mut result = String::from("\"");
+    for character in text.chars() {
+        match character {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            _ if (character as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", character as u32)),
+            _ => result.push(character)
+        }
+    }
+    result.push('"');
+    return result;
+}
+
+pub fn format_json(value: &JsonValue) -> String {
+    match value {
+        JsonValue::Null => "null".to_string(),
+        JsonValue::Boolean(value) => value.to_string(),
+        JsonValue::Number(text) => text.clone(),
+        JsonValue::String(text) => format_string(text),
+        JsonValue::Array(elements) => {
+            let elements: Vec<String> = elements.iter().map(format_json).collect();
+            format!("[{}]", elements.join(", "))
+        },
+        JsonValue::Object(members) => {
+            let members: Vec<String> = members.iter().map(|(key, value)| format!("{}: {}", format_string(key), format_json(value))).collect();
+            format!("{{{}}}", members.join(", "))
+        }
+    }
+}
+
+// Reads a path written as $.key[0]."quoted key"
+pub fn parse_path(text: &str) -> Option<Vec<PathStep>> {
+    let characters: Vec<char> = text.trim().chars().collect();
+    if characters.first() != Some(&'$') {
+        return None;
+    }
+    let mut steps = Vec::new();
+    let mut i = 1;
+    while i < characters.len() {
+        match characters[i] {
+            '.' if characters.get(i + 1) == Some(&'"') => {
+                let end = i + 2 + characters[i + 2..].iter().position(|character| *character == '"')?;
+                steps.push(PathStep::Key(characters[i + 2..end].iter().collect()));
+                i = end + 1;
+            },
+            '.' => {
+                let end = characters[i + 1..].iter().position(|character| *character == '.' || *character == '[')
+                    .map(|position| i + 1 + position).unwrap_or(characters.len());
+                if end == i + 1 {
+                    return None;
+                }
+                steps.push(PathStep::Key(characters[i + 1..end].iter().collect()));
+                i = end;
+            },
+            '[' => {
+                let end = i + 1 + characters[i + 1..].iter().position(|character| *character == ']')?;
+                let index: String = characters[i + 1..end].iter().collect();
+                steps.push(PathStep::Index(index.trim().parse().ok()?));
+                i = end + 1;
+            },
+            _ => return None
+        }
+    }
+    return Some(steps);
+}
@@ -1,17 +1,191 @@
 use std::env;
 use std::fs;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::cmp::Ordering;
+use std::borrow::Cow;
+use std::cell::RefCell;
 pub mod parser;
+pub mod decimal;
+pub mod datetime;
+pub mod json;
+pub mod uuid;
 
 use parser::Parser;
 use parser::ASTNode;
 use parser::DataType;
 use parser::Column;
+use parser::Expression;
+use parser::SelectQuery;
+use parser::FromItem;
+use parser::AlterAction;
+use parser::TableConstraint;
+use parser::ConstraintKind;
+use parser::ReferentialAction;
+use parser::Identity;
+use datetime::Interval;
+use datetime::MICROS_PER_DAY;
+use datetime::MICROS_PER_SECOND;
+use json::JsonValue;
 
 #[derive(Clone)]
 enum Cell {
     INT(i32, u32, bool),
-    STRING(String, u32, bool)
+    STRING(String, u32, bool),
+    // Padded with spaces to the column length
+    CHAR(String, u32, bool),
+    BIGINT(i64, u32, bool),
+    SMALLINT(i16, u32, bool),
+    DOUBLE(f64, u32, bool),
+    BOOLEAN(bool, u32, bool),
+    // Unscaled value and scale
+    DECIMAL(i128, u32, u32, bool),
+    // Days since 1970-01-01
+    DATE(i32, u32, bool),
+    // Microseconds since midnight
+    TIME(i64, u32, bool),
+    // Microseconds since 1970-01-01 00:00:00
+    TIMESTAMP(i64, u32, bool),
+    // Microseconds since 1970-01-01 00:00:00 UTC and the UTC offset in
+    // seconds the value is displayed in
+    TIMESTAMPTZ(i64, i32, u32, bool),
+    INTERVAL(Interval, u32, bool),
+    BLOB(Vec<u8>, u32, bool),
+    JSON(JsonValue, u32, bool),
+    // Position of the label in the declaration and the id of the enum type
+    ENUM(u32, u32, u32, bool),
+    ARRAY(Vec<Cell>, u32, bool),
+    UUID([u8; 16], u32, bool),
+    NULL
+}
+
+// Numeric types widen SMALLINT < INT < BIGINT < DECIMAL < DOUBLE when mixed
+const SMALLINT_RANK: u8 = 1;
+const INT_RANK: u8 = 2;
+const BIGINT_RANK: u8 = 3;
+const DECIMAL_RANK: u8 = 4;
+const DOUBLE_RANK: u8 = 5;
+
+impl Cell {
+    pub fn type_name(&self) -> &str {
+        match self {
+            Cell::INT(_, _, _) => "INT",
+            Cell::STRING(_, _, _) => "VARCHAR",
+            Cell::CHAR(_, _, _) => "CHAR",
+            Cell::BIGINT(_, _, _) => "BIGINT",
+            Cell::SMALLINT(_, _, _) => "SMALLINT",
+            Cell::DOUBLE(_, _, _) => "DOUBLE",
+            Cell::BOOLEAN(_, _, _) => "BOOLEAN",
+            Cell::DECIMAL(_, _, _, _) => "DECIMAL",
+            Cell::DATE(_, _, _) => "DATE",
+            Cell::TIME(_, _, _) => "TIME",
+            Cell::TIMESTAMP(_, _, _) => "TIMESTAMP",
+            Cell::TIMESTAMPTZ(_, _, _, _) => "TIMESTAMPTZ",
+            Cell::INTERVAL(_, _, _) => "INTERVAL",
+            Cell::BLOB(_, _, _) => "BLOB",
+            Cell::JSON(_, _, _) => "JSON",
+            Cell::ENUM(_, _, _, _) => "ENUM",
+            Cell::ARRAY(_, _, _) => "ARRAY",
+            Cell::UUID(_, _, _) => "UUID",
+            Cell::NULL => "NULL"
+        }
+    }
+
+    pub fn numeric_rank(&self) -> Option<u8> {
+        match self {
+            Cell::SMALLINT(_, _, _) => Some(SMALLINT_RANK),
+            Cell::INT(_, _, _) => Some(INT_RANK),
+            Cell::BIGINT(_, _, _) => Some(BIGINT_RANK),
+            Cell::DECIMAL(_, _, _, _) => Some(DECIMAL_RANK),
+            Cell::DOUBLE(_, _, _) => Some(DOUBLE_RANK),
+            _ => None
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Cell::SMALLINT(value, _, _) => Some(*value as i64),
+            Cell::INT(value, _, _) => Some(*value as i64),
+            Cell::BIGINT(value, _, _) => Some(*value),
+            _ => None
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Cell::DOUBLE(value, _, _) => Some(*value),
+            Cell::DECIMAL(value, scale, _, _) => Some(decimal::to_f64(*value, *scale)),
+            _ => self.as_i64().map(|value| value as f64)
+        }
+    }
+
+    pub fn is_datetime(&self) -> bool {
+        return matches!(self, Cell::DATE(_, _, _) | Cell::TIME(_, _, _) | Cell::TIMESTAMP(_, _, _) | Cell::TIMESTAMPTZ(_, _, _, _) | Cell::INTERVAL(_, _, _));
+    }
+
+    // Returns a point in time in microseconds, reading dates as midnight and
+    // timestamps without a time zone as UTC
+    pub fn as_timestamp(&self) -> Option<i64> {
+        match self {
+            Cell::DATE(value, _, _) => Some(*value as i64 * MICROS_PER_DAY),
+            Cell::TIMESTAMP(value, _, _) => Some(*value),
+            Cell::TIMESTAMPTZ(value, _, _, _) => Some(*value),
+            _ => None
+        }
+    }
+
+    // Returns the unscaled value and scale of an exact numeric
+    pub fn as_decimal(&self) -> Option<(i128, u32)> {
+        match self {
+            Cell::DECIMAL(value, scale, _, _) => Some((*value, *scale)),
+            _ => self.as_i64().map(|value| (value as i128, 0))
+        }
+    }
+
+    pub fn render(&self) -> String {
+        match self {
+            Cell::INT(value, _, _) => value.to_string(),
+            Cell::STRING(value, _, _) | Cell::CHAR(value, _, _) => format!("'{}'", value),
+            Cell::BIGINT(value, _, _) => value.to_string(),
+            Cell::SMALLINT(value, _, _) => value.to_string(),
+            Cell::DOUBLE(value, _, _) => value.to_string(),
+            Cell::BOOLEAN(value, _, _) => if *value { "TRUE".to_string() } else { "FALSE".to_string() },
+            Cell::DECIMAL(value, scale, _, _) => decimal::format_decimal(*value, *scale),
+            Cell::DATE(value, _, _) => datetime::format_date(*value as i64),
+            Cell::TIME(value, _, _) => datetime::format_time(*value),
+            Cell::TIMESTAMP(value, _, _) => datetime::format_timestamp(*value),
+            Cell::TIMESTAMPTZ(value, offset, _, _) => {
+                let local = value + *offset as i64 * MICROS_PER_SECOND;
+                format!("{}{}", datetime::format_timestamp(local), datetime::format_offset(*offset))
+            },
+            Cell::INTERVAL(value, _, _) => datetime::format_interval(value),
+            Cell::BLOB(value, _, _) => {
+                let hex: Vec<String> = value.iter().map(|byte| format!("{:02X}", byte)).collect();
+                format!("X'{}'", hex.concat())
+            },
+            Cell::JSON(value, _, _) => json::format_json(value),
+            // The labels live in the database, see Database::render_cell
+            Cell::ENUM(ordinal, _, _, _) => ordinal.to_string(),
+            Cell::ARRAY(elements, _, _) => {
+                let elements: Vec<String> = elements.iter().map(|element| match element {
+                    Cell::NULL => "NULL".to_string(),
+                    _ => element.render()
+                }).collect();
+                format!("{{{}}}", elements.join(","))
+            },
+            Cell::UUID(value, _, _) => uuid::format_uuid(value),
+            Cell::NULL => "".to_string()
+        }
+    }
+
+    // The text a value is indexed under. The same instant is the same key
+    // whatever its offset.
+    pub fn key_text(&self) -> String {
+        match self {
+            Cell::TIMESTAMPTZ(value, _, _, _) => Cell::TIMESTAMPTZ(*value, 0, 0, false).render(),
+            _ => self.render()
+        }
+    }
 }
 
 struct Statement {
@@ -25,13 +199,94 @@ struct Error {
     message: String
 }
 
+const CATALOG_ERROR: i32 = 1;
+const CONSTRAINT_ERROR: i32 = 2;
+const DATA_ERROR: i32 = 3;
+const TYPE_ERROR: i32 = 4;
+const SYNTAX_ERROR: i32 = 5;
+
+impl Error {
+    pub fn new(type1: i32, message: String) -> Error {
+        return Error { type1: type1, message: message };
+    }
+
+    pub fn print(&self) {
+        let label = match self.type1 {
+            CATALOG_ERROR => "Catalog",
+            CONSTRAINT_ERROR => "Constraint",
+            DATA_ERROR => "Data",
+            TYPE_ERROR => "Type",
+            SYNTAX_ERROR => "Syntax",
+            _ => "Unknown"
+        };
+        println!("{} error: {}", label, self.message);
+    }
+}
+
+// Types created with CREATE TYPE and CREATE DOMAIN. An enum's id is its
+// position in the database's list of types.
+enum UserType {
+    Enum { name: String, labels: Vec<String> },
+    Domain { name: String, data_type: DataType, size: u32, nullable: bool, checks: Vec<Expression> }
+}
+
+impl UserType {
+    pub fn name(&self) -> &String {
+        match self {
+            UserType::Enum { name, .. } => name,
+            UserType::Domain { name, .. } => name
+        }
+    }
+}
+
+// A row holds a cell for each column that existed when it was written, so
+// its length is the schema version it was written under. Columns added
+// later are not written into existing rows; those read the value in
+// `missing` instead, which is fixed when the column is added.
 struct Table {
     columns: Vec<Box<Column>>,
     rows: Vec<Vec<Cell>>,
+    missing: Vec<Cell>,
+    indexes: Vec<UniqueIndex>,
+    foreign_keys: Vec<ForeignKey>,
+    checks: Vec<CheckConstraint>,
     row_count: i32,
     cursor: i32
 }
 
+// A PRIMARY KEY or UNIQUE constraint. The keys of all rows are kept in a
+// hash set, so a new row is checked without scanning the table. Rows with
+// a NULL in a key column are left out, as NULLs never conflict.
+struct UniqueIndex {
+    name: String,
+    primary: bool,
+    columns: Vec<String>,
+    keys: HashSet<Vec<String>>
+}
+
+// A FOREIGN KEY constraint. The referenced columns always have a PRIMARY
+// KEY or UNIQUE index, which is where the key of a new row is looked up.
+struct ForeignKey {
+    name: String,
+    columns: Vec<String>,
+    table: String,
+    references: Vec<String>,
+    on_delete: ReferentialAction,
+    on_update: ReferentialAction
+}
+
+// A CHECK constraint. A row passes unless the condition is FALSE.
+struct CheckConstraint {
+    name: String,
+    expression: Expression
+}
+
+impl UniqueIndex {
+    pub fn duplicate_key_error(&self, key: &Vec<String>) -> Error {
+        return Error::new(CONSTRAINT_ERROR, format!("Duplicate key value violates unique constraint '{}': key ({})=({}) already exists", self.name, self.columns.join(", "), key.join(", ")));
+    }
+}
+
 impl Table {
     pub fn insert_row(&mut self, row: Vec<Cell>) {
         self.rows.push(row);
@@ -41,6 +296,69 @@ impl Table {
         self.rows.remove(index as usize);
     }
 
+    // Returns the row with a cell for every column, filling in the columns
+    // added after it was written
+    pub fn read_row<'a>(&self, row: &'a Vec<Cell>) -> Cow<'a, Vec<Cell>> {
+        if row.len() >= self.columns.len() {
+            return Cow::Borrowed(row);
+        }
+        let mut full_row = row.clone();
+        full_row.extend_from_slice(&self.missing[row.len()..]);
+        return Cow::Owned(full_row);
+    }
+
+    // Writes the missing cells into every row, bringing all of them to the
+    // current schema version
+    pub fn materialize_rows(&mut self) {
+        for row in &mut self.rows {
+            if row.len() < self.missing.len() {
+                row.extend_from_slice(&self.missing[row.len()..]);
+            }
+        }
+    }
+
+    // The key of a row in an index, or None when a key column is NULL
+    pub fn index_key(&self, columns: &Vec<String>, row: &Vec<Cell>) -> Option<Vec<String>> {
+        let row = self.read_row(row);
+        let mut key = Vec::new();
+        for name in columns {
+            let index = self.columns.iter().position(|column| column.name == *name)?;
+            key.push(match &row[index] {
+                Cell::NULL => return None,
+                cell => cell.key_text()
+            });
+        }
+        return Some(key);
+    }
+
+    // Collects the keys of all rows, failing with the first key that is
+    // found twice
+    pub fn build_keys(&self, columns: &Vec<String>) -> Result<HashSet<Vec<String>>, Vec<String>> {
+        let mut keys = HashSet::new();
+        for row in &self.rows {
+            if let Some(key) = self.index_key(columns, row) {
+                if keys.contains(&key) {
+                    return Err(key);
+                }
+                keys.insert(key);
+            }
+        }
+        return Ok(keys);
+    }
+
+    pub fn has_constraint(&self, name: &String) -> bool {
+        return self.indexes.iter().any(|unique_index| unique_index.name == *name)
+            || self.foreign_keys.iter().any(|foreign_key| foreign_key.name == *name)
+            || self.checks.iter().any(|check| check.name == *name);
+    }
+
+    // The index on exactly the given columns, in any order
+    pub fn find_index(&self, columns: &Vec<String>) -> Option<&UniqueIndex> {
+        return self.indexes.iter().find(|unique_index| {
+            unique_index.columns.len() == columns.len() && unique_index.columns.iter().all(|column| columns.contains(column))
+        });
+    }
+
     pub fn find_column(&self, name: String) -> Option<&Box<Column>> {
         for column in &self.columns {
             if column.name == name {
@@ -51,204 +369,2637 @@ impl Table {
     }
 }
 
+// The rows produced by a query, with a column for each select item
+struct ResultSet {
+    columns: Vec<Column>,
+    rows: Vec<Vec<Cell>>
+}
+
+// The schema temporary tables are created in
+const TEMPORARY_SCHEMA: &str = "pg_temp";
+
+// A sequence created with CREATE SEQUENCE or for an identity column. The
+// first nextval returns `last_value` itself, later ones add `increment`.
+struct Sequence {
+    last_value: i64,
+    increment: i64,
+    min: i64,
+    max: i64,
+    called: bool,
+    // The value the last nextval returned, read by currval
+    current: Option<i64>
+}
+
 struct Database {
-    tables: HashMap<String, Box<Table>>
+    // Keyed by name, qualified by the schema unless the table is in public
+    tables: HashMap<String, Box<Table>>,
+    schemas: Vec<String>,
+    // The schema unqualified table names refer to, set with USE
+    current_schema: String,
+    user_types: Vec<UserType>,
+    // Sequences advance while expressions are evaluated, which only borrow
+    // the database
+    sequences: RefCell<HashMap<String, Sequence>>,
+    // Over-length strings are rejected in strict mode and truncated
+    // with a warning otherwise
+    strict_mode: bool
 }
 
 impl Database {
     pub fn compareEqCells(&self, cell1: &Cell, cell2: &Cell) -> bool {
-        match cell1 {
-            Cell::INT(value1, _, _) => {
-                match cell2 {
-                    Cell::INT(value2, _, _) => {
-                        return value1 == value2;
-                    },
-                    Cell::STRING(_, _, _) => {
-                        return false;
+        match (cell1, cell2) {
+            // NULLs are not distinct from each other
+            (Cell::NULL, Cell::NULL) => true,
+            _ => matches!(self.compare_cells(cell1, cell2), Ok(Some(Ordering::Equal)))
+        }
+    }
+
+    // Compares two cells, returning None when either of them is NULL
+    pub fn compare_cells(&self, cell1: &Cell, cell2: &Cell) -> Result<Option<Ordering>, Error> {
+        match (cell1, cell2) {
+            (Cell::NULL, _) | (_, Cell::NULL) => Ok(None),
+            // A string compared with another type is read as that type
+            (Cell::STRING(text, _, _), _) if !matches!(cell2, Cell::STRING(_, _, _) | Cell::CHAR(_, _, _)) => {
+                return self.compare_cells(&self.parse_text_like(text, cell2)?, cell2);
+            },
+            (_, Cell::STRING(text, _, _)) if !matches!(cell1, Cell::STRING(_, _, _) | Cell::CHAR(_, _, _)) => {
+                return self.compare_cells(cell1, &self.parse_text_like(text, cell1)?);
+            },
+            (Cell::STRING(value1, _, _), Cell::STRING(value2, _, _)) => Ok(Some(value1.cmp(value2))),
+            // Trailing spaces are not significant when a CHAR is involved
+            (Cell::CHAR(value1, _, _), Cell::CHAR(value2, _, _)) | (Cell::CHAR(value1, _, _), Cell::STRING(value2, _, _))
+                | (Cell::STRING(value1, _, _), Cell::CHAR(value2, _, _)) => Ok(Some(value1.trim_end_matches(' ').cmp(value2.trim_end_matches(' ')))),
+            (Cell::BOOLEAN(value1, _, _), Cell::BOOLEAN(value2, _, _)) => Ok(Some(value1.cmp(value2))),
+            (Cell::TIME(value1, _, _), Cell::TIME(value2, _, _)) => Ok(Some(value1.cmp(value2))),
+            (Cell::BLOB(value1, _, _), Cell::BLOB(value2, _, _)) => Ok(Some(value1.cmp(value2))),
+            (Cell::UUID(value1, _, _), Cell::UUID(value2, _, _)) => Ok(Some(value1.cmp(value2))),
+            // Arrays compare element by element, with NULL elements sorting
+            // after all other values
+            (Cell::ARRAY(elements1, _, _), Cell::ARRAY(elements2, _, _)) => {
+                for (element1, element2) in elements1.iter().zip(elements2.iter()) {
+                    let ordering = match (element1, element2) {
+                        (Cell::NULL, Cell::NULL) => Ordering::Equal,
+                        (Cell::NULL, _) => Ordering::Greater,
+                        (_, Cell::NULL) => Ordering::Less,
+                        _ => match self.compare_cells(element1, element2)? {
+                            Some(ordering) => ordering,
+                            None => return Ok(None)
+                        }
+                    };
+                    if ordering != Ordering::Equal {
+                        return Ok(Some(ordering));
+                    }
+                }
+                return Ok(Some(elements1.len().cmp(&elements2.len())));
+            },
+            // Enum values are ordered by declaration
+            (Cell::ENUM(value1, type1, _, _), Cell::ENUM(value2, type2, _, _)) => {
+                if type1 != type2 {
+                    let (name1, name2) = (self.user_types[*type1 as usize].name(), self.user_types[*type2 as usize].name());
+                    return Err(Error::new(TYPE_ERROR, format!("Cannot compare {} with {}", name1, name2)));
+                }
+                return Ok(Some(value1.cmp(value2)));
+            },
+            // Documents have no natural order, so they compare by their text
+            (Cell::JSON(value1, _, _), Cell::JSON(value2, _, _)) => Ok(Some(json::format_json(value1).cmp(&json::format_json(value2)))),
+            (Cell::INTERVAL(value1, _, _), Cell::INTERVAL(value2, _, _)) => Ok(Some(value1.total_micros().cmp(&value2.total_micros()))),
+            _ if cell1.as_timestamp().is_some() && cell2.as_timestamp().is_some() => {
+                return Ok(Some(cell1.as_timestamp().unwrap().cmp(&cell2.as_timestamp().unwrap())));
+            },
+            _ => {
+                if cell1.numeric_rank().is_none() || cell2.numeric_rank().is_none() {
+                    return Err(Error::new(TYPE_ERROR, format!("Cannot compare {} with {}", cell1.type_name(), cell2.type_name())));
+                }
+                if cell1.numeric_rank() == Some(DOUBLE_RANK) || cell2.numeric_rank() == Some(DOUBLE_RANK) {
+                    return Ok(cell1.as_f64().unwrap().partial_cmp(&cell2.as_f64().unwrap()));
+                }
+                let (value1, scale1) = cell1.as_decimal().unwrap();
+                let (value2, scale2) = cell2.as_decimal().unwrap();
+                let scale = scale1.max(scale2);
+                match (decimal::rescale(value1, scale1, scale), decimal::rescale(value2, scale2, scale)) {
+                    (Some(value1), Some(value2)) => Ok(Some(value1.cmp(&value2))),
+                    _ => Ok(cell1.as_f64().unwrap().partial_cmp(&cell2.as_f64().unwrap()))
+                }
+            }
+        }
+    }
+
+    // Schemas other than public qualify the names of their tables
+    fn qualify(schema: &str, table: &str) -> String {
+        if schema == "public" {
+            return table.to_string();
+        }
+        return format!("{}.{}", schema, table);
+    }
+
+    // The key of a table named in a statement, which is in the current
+    // schema unless the name says otherwise. A temporary table hides a
+    // table of the same name in any schema.
+    fn resolve_name(&self, name: &String) -> String {
+        match name.split_once('.') {
+            Some((schema, table)) => Database::qualify(schema, table),
+            None => {
+                let temporary = Database::qualify(TEMPORARY_SCHEMA, name);
+                if self.tables.contains_key(&temporary) {
+                    return temporary;
+                }
+                Database::qualify(&self.current_schema, name)
+            }
+        }
+    }
+
+    // The key for a table about to be created, which ignores temporary
+    // tables unless it is one itself
+    fn new_table_name(&self, name: &String, temporary: bool) -> Result<String, Error> {
+        match name.split_once('.') {
+            Some((schema, _)) if temporary && schema != TEMPORARY_SCHEMA => {
+                Err(Error::new(CATALOG_ERROR, format!("Cannot create temporary table '{}' in schema '{}'", name, schema)))
+            },
+            Some((schema, table)) => Ok(Database::qualify(schema, table)),
+            None if temporary => Ok(Database::qualify(TEMPORARY_SCHEMA, name)),
+            None => Ok(Database::qualify(&self.current_schema, name))
+        }
+    }
+
+    // The schema and the unqualified name of a table key
+    fn split_name(name: &String) -> (&str, &str) {
+        return name.split_once('.').unwrap_or(("public", name.as_str()));
+    }
+
+    // A table key written so that it resolves back to the same table from
+    // the current schema
+    fn display_name(&self, name: &String) -> String {
+        let (schema, table) = Database::split_name(name);
+        if schema == self.current_schema {
+            return table.to_string();
+        }
+        return format!("{}.{}", schema, table);
+    }
+
+    // Temporary tables go when the session ends. Only other temporary
+    // tables can reference them, and those go as well.
+    pub fn drop_temporary_tables(&mut self) {
+        let table_names: Vec<String> = self.tables.keys().filter(|name| Database::split_name(name).0 == TEMPORARY_SCHEMA).cloned().collect();
+        for name in table_names {
+            let table = self.tables.remove(&name).unwrap();
+            for column in &table.columns {
+                self.drop_identity_sequence(column);
+            }
+        }
+    }
+
+    pub fn create_schema(&mut self, name: String, if_not_exists: bool) -> Result<(), Error> {
+        if self.schemas.contains(&name) {
+            if if_not_exists {
+                println!("Notice: Schema '{}' already exists, skipping", name);
+                return Ok(());
+            }
+            return Err(Error::new(CATALOG_ERROR, format!("Schema already exists: {}", name)));
+        }
+        self.schemas.push(name);
+        return Ok(());
+    }
+
+    // A schema with tables in it is only dropped with CASCADE, which drops
+    // the tables too. Dropping the current schema goes back to public.
+    pub fn drop_schema(&mut self, name: String, if_exists: bool, cascade: bool) -> Result<(), Error> {
+        if !self.schemas.contains(&name) {
+            if if_exists {
+                println!("Notice: Schema '{}' does not exist, skipping", name);
+                return Ok(());
+            }
+            return Err(Error::new(CATALOG_ERROR, format!("Schema not found: {}", name)));
+        }
+        if name == "public" || name == "information_schema" || name == TEMPORARY_SCHEMA {
+            return Err(Error::new(CATALOG_ERROR, format!("Cannot drop schema '{}'", name)));
+        }
+        let mut table_names: Vec<String> = self.tables.keys().filter(|table_name| Database::split_name(table_name).0 == name).cloned().collect();
+        table_names.sort();
+        if let Some(table_name) = table_names.first() {
+            if !cascade {
+                return Err(Error::new(CATALOG_ERROR, format!("Cannot drop schema '{}' because table '{}' depends on it", name, table_name)));
+            }
+        }
+        for table_name in table_names {
+            println!("Notice: Drop cascades to table '{}'", table_name);
+            self.drop_table(table_name, false, true)?;
+        }
+        self.schemas.retain(|schema| *schema != name);
+        if self.current_schema == name {
+            self.current_schema = "public".to_string();
+        }
+        return Ok(());
+    }
+
+    pub fn use_schema(&mut self, name: String) -> Result<(), Error> {
+        if !self.schemas.contains(&name) {
+            return Err(Error::new(CATALOG_ERROR, format!("Schema not found: {}", name)));
+        }
+        self.current_schema = name;
+        return Ok(());
+    }
+
+    // Creates a table with its columns and constraints. A column or
+    // constraint that fails is reported and no table is created.
+    pub fn create_table(&mut self, name: String, temporary: bool, if_not_exists: bool, columns: Vec<Column>, constraints: Vec<TableConstraint>) -> Result<(), Error> {
+        let name = self.new_table_name(&name, temporary)?;
+        self.check_writable(&name)?;
+        let (schema, _) = Database::split_name(&name);
+        if !self.schemas.iter().any(|schema_name| schema_name == schema) {
+            return Err(Error::new(CATALOG_ERROR, format!("Schema not found: {}", schema)));
+        }
+        if self.tables.contains_key(&name) {
+            if if_not_exists {
+                println!("Notice: Table '{}' already exists, skipping", name);
+                return Ok(());
+            }
+            return Err(Error::new(CATALOG_ERROR, format!("Table already exists: {}", name)));
+        }
+        self.tables.insert(name.clone(), Box::new(Table { cursor: 0, row_count: 0, columns: Vec::new(), rows: Vec::new(), missing: Vec::new(), indexes: Vec::new(), foreign_keys: Vec::new(), checks: Vec::new() }));
+        // A table is only kept when all of it could be created, so that a
+        // failed CREATE TABLE IF NOT EXISTS is tried again on the next run
+        if let Err(error) = self.add_table_definition(&name, columns, constraints) {
+            let table = self.tables.remove(&name).unwrap();
+            for column in &table.columns {
+                self.drop_identity_sequence(column);
+            }
+            return Err(error);
+        }
+        return Ok(());
+    }
+
+    fn add_table_definition(&mut self, name: &String, columns: Vec<Column>, constraints: Vec<TableConstraint>) -> Result<(), Error> {
+        for column in columns {
+            self.add_table_column(name.clone(), Box::new(column))?;
+        }
+        for constraint in constraints {
+            self.add_constraint(name.clone(), constraint)?;
+        }
+        return Ok(());
+    }
+
+    // The foreign keys of other tables that reference a table, as pairs of
+    // the referencing table and the constraint name
+    fn referencing_constraints(&self, name: &String) -> Vec<(String, String)> {
+        let mut constraints = Vec::new();
+        for (table_name, table) in &self.tables {
+            if table_name == name {
+                continue;
+            }
+            for foreign_key in table.foreign_keys.iter().filter(|foreign_key| foreign_key.table == *name) {
+                constraints.push((table_name.clone(), foreign_key.name.clone()));
+            }
+        }
+        constraints.sort();
+        return constraints;
+    }
+
+    // A table referenced by another table's foreign key is only dropped
+    // with CASCADE, which drops those foreign keys but not the tables
+    pub fn drop_table(&mut self, name: String, if_exists: bool, cascade: bool) -> Result<(), Error> {
+        let name = self.resolve_name(&name);
+        self.check_writable(&name)?;
+        if !self.tables.contains_key(&name) {
+            if if_exists {
+                println!("Notice: Table '{}' does not exist, skipping", name);
+                return Ok(());
+            }
+            return Err(Error::new(CATALOG_ERROR, format!("Table not found: {}", name)));
+        }
+        let constraints = self.referencing_constraints(&name);
+        if let Some((table_name, constraint_name)) = constraints.first() {
+            if !cascade {
+                return Err(Error::new(CATALOG_ERROR, format!("Cannot drop table '{}' because constraint '{}' on table '{}' depends on it", name, constraint_name, table_name)));
+            }
+        }
+        for (table_name, constraint_name) in constraints {
+            println!("Notice: Drop cascades to constraint '{}' on table '{}'", constraint_name, table_name);
+            self.tables.get_mut(&table_name).unwrap().foreign_keys.retain(|foreign_key| foreign_key.name != constraint_name);
+        }
+        let table = self.tables.remove(&name).unwrap();
+        for column in &table.columns {
+            self.drop_identity_sequence(column);
+        }
+        return Ok(());
+    }
+
+    // A table referenced by another table's foreign key is only truncated
+    // with CASCADE, which truncates the referencing tables as well
+    pub fn truncate_table(&mut self, name: String, cascade: bool) -> Result<(), Error> {
+        let name = self.resolve_name(&name);
+        self.check_writable(&name)?;
+        if !self.tables.contains_key(&name) {
+            return Err(Error::new(CATALOG_ERROR, format!("Table not found: {}", name)));
+        }
+        let mut names = vec![name.clone()];
+        let mut i = 0;
+        while i < names.len() {
+            for (table_name, _) in self.referencing_constraints(&names[i]) {
+                if names.contains(&table_name) {
+                    continue;
+                }
+                if !cascade {
+                    return Err(Error::new(CONSTRAINT_ERROR, format!("Cannot truncate table '{}' because table '{}' references it in a foreign key constraint", names[i], table_name)));
+                }
+                println!("Notice: Truncate cascades to table '{}'", table_name);
+                names.push(table_name);
+            }
+            i += 1;
+        }
+        for name in names {
+            let table = self.tables.get_mut(&name).expect("Could not find table");
+            table.rows.clear();
+            table.row_count = 0;
+            for index in &mut table.indexes {
+                index.keys.clear();
+            }
+        }
+        return Ok(());
+    }
+
+    pub fn add_table_column(&mut self, name: String, mut column: Box<Column>) -> Result<(), Error> {
+        if let DataType::USERDEFINED(type_name) = &column.data_type {
+            if self.find_user_type(type_name).is_none() {
+                return Err(Error::new(CATALOG_ERROR, format!("Type not found: {}", type_name)));
+            }
+        }
+        // An identity column takes its values from a sequence of its own
+        if column.identity.is_some() {
+            let max = match column.data_type {
+                DataType::SMALLINT => i16::MAX as i64,
+                DataType::INT => i32::MAX as i64,
+                DataType::BIGINT => i64::MAX,
+                _ => return Err(Error::new(TYPE_ERROR, format!("Identity column '{}' must be of an integer type", column.name)))
+            };
+            // Named table_column_seq, with a number added when a renamed
+            // table or column left a sequence by that name behind
+            let base = format!("{}_{}_seq", name, column.name);
+            let mut sequence_name = base.clone();
+            let mut number = 1;
+            while self.sequences.borrow().contains_key(&sequence_name) {
+                sequence_name = format!("{}{}", base, number);
+                number += 1;
+            }
+            self.create_sequence(sequence_name.clone(), None, 1, None, Some(max))?;
+            column.default = Some(Expression::Function { name: "NEXTVAL".to_string(), arguments: vec![Expression::Text(sequence_name)] });
+            column.nullable = false;
+            // The sequence goes again when the column cannot be added
+            let identity_column = (*column).clone();
+            let result = self.fill_table_column(&name, column);
+            if result.is_err() {
+                self.drop_identity_sequence(&identity_column);
+            }
+            return result;
+        }
+        return self.fill_table_column(&name, column);
+    }
+
+    // Gives the rows already in a table their value for a new column, and
+    // adds the column
+    fn fill_table_column(&mut self, name: &String, column: Box<Column>) -> Result<(), Error> {
+        let volatile = column.default.as_ref().map_or(false, |default| self.is_volatile(default));
+        // Existing rows read the default as it is now, without being rewritten
+        let missing = match &column.default {
+            Some(default) if !volatile => {
+                let value = self.evaluate_expression(default, &Vec::new(), &Vec::new())?;
+                self.coerce_to_column(value, &column)?
+            },
+            _ => Cell::NULL
+        };
+        let table = self.tables.get(name).expect("Could not find table");
+        // A volatile default gives every existing row a value of its own, so
+        // those rows are written out instead
+        let mut values = None;
+        if let Some(default) = column.default.as_ref().filter(|_| volatile) {
+            let mut row_values = Vec::new();
+            for _ in &table.rows {
+                let value = self.evaluate_expression(default, &Vec::new(), &Vec::new())?;
+                row_values.push(self.coerce_to_column(value, &column)?);
+            }
+            values = Some(row_values);
+        }
+        let has_null = match &values {
+            Some(values) => values.iter().any(|value| matches!(value, Cell::NULL)),
+            None => !table.rows.is_empty() && matches!(missing, Cell::NULL)
+        };
+        if !column.nullable && has_null {
+            return Err(Error::new(CONSTRAINT_ERROR, format!("Column '{}' of table '{}' contains null values", column.name, name)));
+        }
+        let table = self.tables.get_mut(name).unwrap();
+        match values {
+            Some(values) => {
+                table.materialize_rows();
+                for (row, value) in table.rows.iter_mut().zip(values) {
+                    row.push(value);
+                }
+                table.missing.push(Cell::NULL);
+            },
+            None => table.missing.push(missing)
+        }
+        table.columns.push(column);
+        return Ok(());
+    }
+
+    pub fn alter_table(&mut self, name: String, action: AlterAction) -> Result<(), Error> {
+        let name = self.resolve_name(&name);
+        self.check_writable(&name)?;
+        if !self.tables.contains_key(&name) {
+            return Err(Error::new(CATALOG_ERROR, format!("Table not found: {}", name)));
+        }
+        match action {
+            AlterAction::AddColumns(columns, constraints) => {
+                for column in columns {
+                    if let Err(error) = self.add_table_column(name.clone(), Box::new(column)) {
+                        error.print();
+                    }
+                }
+                for constraint in constraints {
+                    if let Err(error) = self.add_constraint(name.clone(), constraint) {
+                        error.print();
+                    }
+                }
+                return Ok(());
+            },
+            AlterAction::AddConstraint(constraint) => self.add_constraint(name, constraint),
+            AlterAction::RenameColumn { from, to } => self.rename_table_column(name, from, to),
+            AlterAction::RenameTable(new_name) => self.rename_table(name, new_name),
+            AlterAction::DropColumn(column) => self.drop_table_column(name, column),
+            AlterAction::AlterColumnType { column, data_type, size, using } => self.alter_column_type(name, column, data_type, size, using),
+            AlterAction::SetNotNull(column) => {
+                let table = self.tables.get(&name).unwrap();
+                let index = self.column_index(table, &name, &column)?;
+                if table.rows.iter().any(|row| matches!(table.read_row(row)[index], Cell::NULL)) {
+                    return Err(Error::new(CONSTRAINT_ERROR, format!("Column '{}' of table '{}' contains null values", column, name)));
+                }
+                self.tables.get_mut(&name).unwrap().columns[index].nullable = false;
+                return Ok(());
+            },
+            AlterAction::DropNotNull(column) => {
+                let table = self.tables.get(&name).unwrap();
+                let index = self.column_index(table, &name, &column)?;
+                if table.indexes.iter().any(|unique_index| unique_index.primary && unique_index.columns.contains(&column)) {
+                    return Err(Error::new(CONSTRAINT_ERROR, format!("Column '{}' of table '{}' is in a primary key", column, name)));
+                }
+                self.tables.get_mut(&name).unwrap().columns[index].nullable = true;
+                return Ok(());
+            },
+            AlterAction::SetDefault { column, default } => {
+                let table = self.tables.get(&name).unwrap();
+                let index = self.column_index(table, &name, &column)?;
+                if table.columns[index].identity.is_some() {
+                    return Err(Error::new(CATALOG_ERROR, format!("Column '{}' of table '{}' is an identity column", column, name)));
+                }
+                // Check that the default fits the column before storing it,
+                // unless evaluating it would advance a sequence
+                if !self.is_volatile(&default) {
+                    let value = self.evaluate_expression(&default, &Vec::new(), &Vec::new())?;
+                    self.coerce_to_column(value, &table.columns[index])?;
+                }
+                self.tables.get_mut(&name).unwrap().columns[index].default = Some(default);
+                return Ok(());
+            },
+            AlterAction::DropDefault(column) => {
+                let table = self.tables.get(&name).unwrap();
+                let index = self.column_index(table, &name, &column)?;
+                if table.columns[index].identity.is_some() {
+                    return Err(Error::new(CATALOG_ERROR, format!("Column '{}' of table '{}' is an identity column", column, name)));
+                }
+                self.tables.get_mut(&name).unwrap().columns[index].default = None;
+                return Ok(());
+            }
+        }
+    }
+
+    fn column_index(&self, table: &Table, table_name: &String, column_name: &String) -> Result<usize, Error> {
+        match table.columns.iter().position(|column| column.name == *column_name) {
+            Some(index) => Ok(index),
+            None => Err(Error::new(CATALOG_ERROR, format!("Column '{}' of table '{}' does not exist", column_name, table_name)))
+        }
+    }
+
+    pub fn drop_table_column(&mut self, name: String, column: String) -> Result<(), Error> {
+        let index = self.column_index(self.tables.get(&name).unwrap(), &name, &column)?;
+        for (table_name, table) in &self.tables {
+            for foreign_key in &table.foreign_keys {
+                if foreign_key.table == name && foreign_key.references.contains(&column) {
+                    return Err(Error::new(CATALOG_ERROR, format!("Cannot drop column '{}' of table '{}' because constraint '{}' on table '{}' depends on it", column, name, foreign_key.name, table_name)));
+                }
+            }
+        }
+        let table = self.tables.get_mut(&name).unwrap();
+        let dropped = table.columns.remove(index);
+        table.missing.remove(index);
+        for row in &mut table.rows {
+            if index < row.len() {
+                row.remove(index);
+            }
+        }
+        // Constraints on the column go with it
+        table.indexes.retain(|unique_index| !unique_index.columns.contains(&column));
+        table.foreign_keys.retain(|foreign_key| !foreign_key.columns.contains(&column));
+        table.checks.retain(|check| !Database::references_column(&check.expression, &column));
+        self.drop_identity_sequence(&dropped);
+        return Ok(());
+    }
+
+    // Converts every stored value of a column to a new type, either directly
+    // or through the USING expression evaluated against the row. Nothing is
+    // changed unless all values convert.
+    pub fn alter_column_type(&mut self, name: String, column_name: String, data_type: DataType, size: u32, using: Option<Expression>) -> Result<(), Error> {
+        let table = self.tables.get(&name).unwrap();
+        let index = self.column_index(table, &name, &column_name)?;
+        if let DataType::USERDEFINED(type_name) = &data_type {
+            if self.find_user_type(type_name).is_none() {
+                return Err(Error::new(CATALOG_ERROR, format!("Type not found: {}", type_name)));
+            }
+        }
+        self.tables.get_mut(&name).unwrap().materialize_rows();
+        let table = self.tables.get(&name).unwrap();
+        let mut column = table.columns[index].clone();
+        column.data_type = data_type;
+        column.size = size;
+        let mut values = Vec::new();
+        for row in &table.rows {
+            let value = match &using {
+                Some(expression) => self.evaluate_expression(expression, &table.columns, row)?,
+                None => row[index].clone()
+            };
+            if let Cell::NULL = value {
+                if !column.nullable {
+                    return Err(Error::new(CONSTRAINT_ERROR, format!("Column '{}' of table '{}' contains null values", column_name, name)));
+                }
+            }
+            values.push(self.coerce_to_column(value, &column)?);
+        }
+        let table = self.tables.get_mut(&name).unwrap();
+        let old_values: Vec<Cell> = table.rows.iter_mut().zip(values).map(|(row, value)| std::mem::replace(&mut row[index], value)).collect();
+        let old_column = std::mem::replace(&mut table.columns[index], column);
+        let validated = self.validate_converted_column(&name, &column_name);
+        let table = self.tables.get_mut(&name).unwrap();
+        let rebuilt = match validated {
+            Ok(rebuilt) => rebuilt,
+            Err(error) => {
+                for (row, value) in table.rows.iter_mut().zip(old_values) {
+                    row[index] = value;
+                }
+                table.columns[index] = old_column;
+                return Err(error);
+            }
+        };
+        for (index_name, keys) in rebuilt {
+            table.indexes.iter_mut().find(|unique_index| unique_index.name == index_name).unwrap().keys = keys;
+        }
+        // Rows are all at the current version, so the missing value is
+        // only kept to line up with the columns
+        table.missing[index] = Cell::NULL;
+        return Ok(());
+    }
+
+    // Checks the constraints of a table after a column changed type and
+    // returns the rebuilt keys of the indexes on it. Values that were
+    // distinct can become equal in the new type.
+    fn validate_converted_column(&self, name: &String, column_name: &String) -> Result<Vec<(String, HashSet<Vec<String>>)>, Error> {
+        let table = self.tables.get(name).unwrap();
+        let mut rebuilt = Vec::new();
+        for unique_index in table.indexes.iter().filter(|unique_index| unique_index.columns.contains(column_name)) {
+            match table.build_keys(&unique_index.columns) {
+                Ok(keys) => rebuilt.push((unique_index.name.clone(), keys)),
+                Err(key) => return Err(unique_index.duplicate_key_error(&key))
+            }
+        }
+        for check in &table.checks {
+            self.check_rows(name, table, check)?;
+        }
+        // Foreign keys from and to the column must still compare
+        for foreign_key in table.foreign_keys.iter().filter(|foreign_key| foreign_key.columns.contains(column_name)) {
+            self.check_foreign_key_types(table, self.tables.get(&foreign_key.table).unwrap(), foreign_key)?;
+        }
+        for child in self.tables.values() {
+            for foreign_key in child.foreign_keys.iter().filter(|foreign_key| foreign_key.table == *name && foreign_key.references.contains(column_name)) {
+                self.check_foreign_key_types(child, table, foreign_key)?;
+            }
+        }
+        return Ok(rebuilt);
+    }
+
+    pub fn rename_table_column(&mut self, name: String, from: String, to: String) -> Result<(), Error> {
+        let table = self.tables.get_mut(&name).expect("Could not find table");
+        if table.find_column(to.clone()).is_some() {
+            return Err(Error::new(CATALOG_ERROR, format!("Column '{}' of table '{}' already exists", to, name)));
+        }
+        match table.columns.iter_mut().find(|column| column.name == from) {
+            Some(column) => column.name = to.clone(),
+            None => return Err(Error::new(CATALOG_ERROR, format!("Column '{}' of table '{}' does not exist", from, name)))
+        }
+        for unique_index in &mut table.indexes {
+            for column in &mut unique_index.columns {
+                if *column == from {
+                    *column = to.clone();
+                }
+            }
+        }
+        for foreign_key in &mut table.foreign_keys {
+            for column in &mut foreign_key.columns {
+                if *column == from {
+                    *column = to.clone();
+                }
+            }
+        }
+        for check in &mut table.checks {
+            Database::rename_column_reference(&mut check.expression, &from, &to);
+        }
+        for (_, table) in &mut self.tables {
+            for foreign_key in table.foreign_keys.iter_mut().filter(|foreign_key| foreign_key.table == name) {
+                for column in &mut foreign_key.references {
+                    if *column == from {
+                        *column = to.clone();
+                    }
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    // Adds a PRIMARY KEY or UNIQUE constraint, building its index from the
+    // rows already in the table. Unnamed constraints are named the way
+    // PostgreSQL names them, table_pkey and table_column_key.
+    pub fn add_constraint(&mut self, name: String, constraint: TableConstraint) -> Result<(), Error> {
+        let table = self.tables.get(&name).expect("Could not find table");
+        let (primary, columns) = match constraint.kind {
+            ConstraintKind::PrimaryKey(columns) => (true, columns),
+            ConstraintKind::Unique(columns) => (false, columns),
+            ConstraintKind::ForeignKey { columns, table, references, on_delete, on_update } => {
+                let foreign_key = ForeignKey {
+                    name: constraint.name.unwrap_or(format!("{}_{}_fkey", Database::split_name(&name).1, columns.join("_"))),
+                    columns: columns,
+                    table: self.resolve_name(&table),
+                    references: references,
+                    on_delete: on_delete,
+                    on_update: on_update
+                };
+                return self.add_foreign_key(name, foreign_key);
+            },
+            ConstraintKind::Check { expression, column } => return self.add_check(name, constraint.name, expression, column)
+        };
+        let mut positions = Vec::new();
+        for column in &columns {
+            positions.push(self.column_index(table, &name, column)?);
+        }
+        let constraint_name = match constraint.name {
+            Some(constraint_name) => constraint_name,
+            None if primary => format!("{}_pkey", Database::split_name(&name).1),
+            None => format!("{}_{}_key", Database::split_name(&name).1, columns.join("_"))
+        };
+        if primary && table.indexes.iter().any(|unique_index| unique_index.primary) {
+            return Err(Error::new(CATALOG_ERROR, format!("Multiple primary keys for table '{}' are not allowed", name)));
+        }
+        if table.has_constraint(&constraint_name) {
+            return Err(Error::new(CATALOG_ERROR, format!("Constraint '{}' for table '{}' already exists", constraint_name, name)));
+        }
+        if primary {
+            for (column, position) in columns.iter().zip(&positions) {
+                if table.rows.iter().any(|row| matches!(table.read_row(row)[*position], Cell::NULL)) {
+                    return Err(Error::new(CONSTRAINT_ERROR, format!("Column '{}' of table '{}' contains null values", column, name)));
+                }
+            }
+        }
+        let mut unique_index = UniqueIndex { name: constraint_name, primary: primary, columns: columns, keys: HashSet::new() };
+        unique_index.keys = match table.build_keys(&unique_index.columns) {
+            Ok(keys) => keys,
+            Err(key) => return Err(unique_index.duplicate_key_error(&key))
+        };
+        let table = self.tables.get_mut(&name).unwrap();
+        // The columns of a primary key are NOT NULL
+        if primary {
+            for position in positions {
+                table.columns[position].nullable = false;
+            }
+        }
+        table.indexes.push(unique_index);
+        return Ok(());
+    }
+
+    // Adds a FOREIGN KEY constraint after checking that the referenced
+    // columns are unique and that every row already in the table refers to
+    // an existing row
+    fn add_foreign_key(&mut self, name: String, mut foreign_key: ForeignKey) -> Result<(), Error> {
+        let table = self.tables.get(&name).unwrap();
+        let parent = match self.tables.get(&foreign_key.table) {
+            Some(parent) => parent,
+            None => return Err(Error::new(CATALOG_ERROR, format!("Table not found: {}", foreign_key.table)))
+        };
+        let temporary = |name: &String| Database::split_name(name).0 == TEMPORARY_SCHEMA;
+        if temporary(&foreign_key.table) && !temporary(&name) {
+            return Err(Error::new(CATALOG_ERROR, format!("Foreign key '{}' of permanent table '{}' cannot reference temporary table '{}'", foreign_key.name, name, foreign_key.table)));
+        }
+        // There are no DELETE or UPDATE statements, so only the actions
+        // that leave referencing rows alone can be honoured
+        for (event, action) in [("DELETE", &foreign_key.on_delete), ("UPDATE", &foreign_key.on_update)] {
+            if *action != ReferentialAction::NoAction && *action != ReferentialAction::Restrict {
+                return Err(Error::new(CONSTRAINT_ERROR, format!("ON {} {} is not supported for foreign key '{}'", event, action, foreign_key.name)));
+            }
+        }
+        if foreign_key.references.is_empty() {
+            match parent.indexes.iter().find(|unique_index| unique_index.primary) {
+                Some(primary_key) => foreign_key.references = primary_key.columns.clone(),
+                None => return Err(Error::new(CATALOG_ERROR, format!("There is no primary key for referenced table '{}'", foreign_key.table)))
+            }
+        }
+        for column in &foreign_key.columns {
+            self.column_index(table, &name, column)?;
+        }
+        for column in &foreign_key.references {
+            self.column_index(parent, &foreign_key.table, column)?;
+        }
+        if foreign_key.columns.len() != foreign_key.references.len() {
+            return Err(Error::new(CATALOG_ERROR, "Number of referencing and referenced columns for foreign key disagree".to_string()));
+        }
+        self.check_foreign_key_types(table, parent, &foreign_key)?;
+        if parent.find_index(&foreign_key.references).is_none() {
+            return Err(Error::new(CATALOG_ERROR, format!("There is no unique constraint matching given keys for referenced table '{}'", foreign_key.table)));
+        }
+        if table.has_constraint(&foreign_key.name) {
+            return Err(Error::new(CATALOG_ERROR, format!("Constraint '{}' for table '{}' already exists", foreign_key.name, name)));
+        }
+        for row in &table.rows {
+            self.check_foreign_key(&name, table, &foreign_key, row)?;
+        }
+        self.tables.get_mut(&name).unwrap().foreign_keys.push(foreign_key);
+        return Ok(());
+    }
+
+    // Adds a CHECK constraint once every row already in the table passes it.
+    // Unnamed checks are named table_column_check when declared on a column
+    // and table_check otherwise, with a number added to keep them unique.
+    fn add_check(&mut self, name: String, constraint_name: Option<String>, expression: Expression, column: Option<String>) -> Result<(), Error> {
+        let table = self.tables.get(&name).unwrap();
+        let constraint_name = match constraint_name {
+            Some(constraint_name) => {
+                if table.has_constraint(&constraint_name) {
+                    return Err(Error::new(CATALOG_ERROR, format!("Constraint '{}' for table '{}' already exists", constraint_name, name)));
+                }
+                constraint_name
+            },
+            None => {
+                let base = match &column {
+                    Some(column) => format!("{}_{}_check", Database::split_name(&name).1, column),
+                    None => format!("{}_check", Database::split_name(&name).1)
+                };
+                let mut constraint_name = base.clone();
+                let mut number = 1;
+                while table.has_constraint(&constraint_name) {
+                    constraint_name = format!("{}{}", base, number);
+                    number += 1;
+                }
+                constraint_name
+            }
+        };
+        let check = CheckConstraint { name: constraint_name, expression: expression };
+        // Evaluating against a row of NULLs finds unknown columns even when
+        // the table is empty
+        let null_row = vec![Cell::NULL; table.columns.len()];
+        match self.evaluate_expression(&check.expression, &table.columns, &null_row)? {
+            Cell::BOOLEAN(_, _, _) | Cell::NULL => {},
+            result => return Err(Error::new(TYPE_ERROR, format!("Check constraint '{}' must be BOOLEAN, not {}", check.name, result.type_name())))
+        }
+        self.check_rows(&name, table, &check)?;
+        self.tables.get_mut(&name).unwrap().checks.push(check);
+        return Ok(());
+    }
+
+    // Checks the rows already in a table against a check constraint
+    fn check_rows(&self, name: &String, table: &Table, check: &CheckConstraint) -> Result<(), Error> {
+        for row in &table.rows {
+            if let Err(error) = self.check_row(name, table, check, row) {
+                if error.type1 == CONSTRAINT_ERROR {
+                    return Err(Error::new(CONSTRAINT_ERROR, format!("Check constraint '{}' of table '{}' is violated by some row", check.name, name)));
+                }
+                return Err(error);
+            }
+        }
+        return Ok(());
+    }
+
+    fn check_row(&self, name: &String, table: &Table, check: &CheckConstraint, row: &Vec<Cell>) -> Result<(), Error> {
+        match self.evaluate_expression(&check.expression, &table.columns, &table.read_row(row))? {
+            Cell::BOOLEAN(false, _, _) => Err(Error::new(CONSTRAINT_ERROR, format!("New row for table '{}' violates check constraint '{}'", name, check.name))),
+            Cell::BOOLEAN(true, _, _) | Cell::NULL => Ok(()),
+            result => Err(Error::new(TYPE_ERROR, format!("Check constraint '{}' must be BOOLEAN, not {}", check.name, result.type_name())))
+        }
+    }
+
+    // Checks that a row of a table refers to an existing row of the table
+    // its foreign key references. A key with a NULL in it refers to
+    // nothing and always passes.
+    fn check_foreign_key(&self, name: &String, table: &Table, foreign_key: &ForeignKey, row: &Vec<Cell>) -> Result<(), Error> {
+        let row = table.read_row(row);
+        let parent = self.tables.get(&foreign_key.table).unwrap();
+        let parent_index = parent.find_index(&foreign_key.references).unwrap();
+        let mut values = Vec::new();
+        for column in &foreign_key.columns {
+            let value = row[self.column_index(table, name, column)?].clone();
+            if let Cell::NULL = value {
+                return Ok(());
+            }
+            values.push(value);
+        }
+        // The key is built in the order of the referenced index, with each
+        // value converted to the type of the column it refers to
+        let mut key = Vec::new();
+        for column in &parent_index.columns {
+            let position = foreign_key.references.iter().position(|reference| reference == column).unwrap();
+            let parent_column = &parent.columns[self.column_index(parent, &foreign_key.table, column)?];
+            // A value the referenced column cannot hold is not in its table
+            match self.coerce_to_column(values[position].clone(), parent_column) {
+                Ok(value) => key.push(value.key_text()),
+                Err(_) => break
+            }
+        }
+        if key.len() == parent_index.columns.len() {
+            if parent_index.keys.contains(&key) {
+                return Ok(());
+            }
+            // A row may refer to itself
+            if foreign_key.table == *name && table.index_key(&parent_index.columns, &row) == Some(key) {
+                return Ok(());
+            }
+        }
+        let values: Vec<String> = values.iter().map(|value| value.key_text()).collect();
+        return Err(Error::new(CONSTRAINT_ERROR, format!("Insert or update on table '{}' violates foreign key constraint '{}': key ({})=({}) is not present in table '{}'",
+            name, foreign_key.name, foreign_key.columns.join(", "), values.join(", "), foreign_key.table)));
+    }
+
+    // The key columns of a foreign key have to hold values that can be
+    // compared with those of the columns they refer to
+    fn check_foreign_key_types(&self, table: &Table, parent: &Table, foreign_key: &ForeignKey) -> Result<(), Error> {
+        for (column, reference) in foreign_key.columns.iter().zip(&foreign_key.references) {
+            let column = table.find_column(column.clone()).unwrap();
+            let reference = parent.find_column(reference.clone()).unwrap();
+            if !self.comparable_types(&column.data_type, &reference.data_type) {
+                return Err(Error::new(TYPE_ERROR, format!("Foreign key '{}' cannot be implemented: key columns '{}' and '{}' are of incompatible types {} and {}",
+                    foreign_key.name, column.name, reference.name, column.data_type, reference.data_type)));
+            }
+        }
+        return Ok(());
+    }
+
+    // Whether values of two types can be compared: numbers with numbers,
+    // strings with strings, and other types only with themselves. Domains
+    // compare as their base type.
+    fn comparable_types(&self, type1: &DataType, type2: &DataType) -> bool {
+        let base = |data_type: &DataType| match data_type {
+            DataType::USERDEFINED(name) => match self.find_user_type(name) {
+                Some((_, UserType::Domain { data_type, .. })) => data_type.clone(),
+                _ => data_type.clone()
+            },
+            _ => data_type.clone()
+        };
+        let category = |data_type: &DataType| match data_type {
+            DataType::SMALLINT | DataType::INT | DataType::BIGINT | DataType::DECIMAL(_, _) | DataType::DOUBLE => Some("numeric"),
+            DataType::STRING | DataType::CHAR | DataType::TEXT => Some("string"),
+            DataType::TIMESTAMP | DataType::TIMESTAMPTZ => Some("timestamp"),
+            _ => None
+        };
+        match (base(type1), base(type2)) {
+            (DataType::ARRAY(element1), DataType::ARRAY(element2)) => self.comparable_types(&element1, &element2),
+            (type1, type2) => match (category(&type1), category(&type2)) {
+                (Some(category1), Some(category2)) => category1 == category2,
+                _ => type1 == type2
+            }
+        }
+    }
+
+    // A table stays in its schema unless the new name gives another one
+    pub fn rename_table(&mut self, name: String, new_name: String) -> Result<(), Error> {
+        let new_name = match new_name.contains('.') {
+            true => self.resolve_name(&new_name),
+            false => Database::qualify(Database::split_name(&name).0, &new_name)
+        };
+        if self.tables.contains_key(&new_name) {
+            return Err(Error::new(CATALOG_ERROR, format!("Table already exists: {}", new_name)));
+        }
+        let table = self.tables.remove(&name).expect("Could not find table");
+        self.tables.insert(new_name.clone(), table);
+        for (_, table) in &mut self.tables {
+            for foreign_key in &mut table.foreign_keys {
+                if foreign_key.table == name {
+                    foreign_key.table = new_name.clone();
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    // A sequence that a column default takes its values from, such as the
+    // sequence of an identity column, cannot be dropped
+    pub fn drop_sequence(&mut self, name: String, if_exists: bool) -> Result<(), Error> {
+        if !self.sequences.borrow().contains_key(&name) {
+            if if_exists {
+                println!("Notice: Sequence '{}' does not exist, skipping", name);
+                return Ok(());
+            }
+            return Err(Error::new(CATALOG_ERROR, format!("Sequence not found: {}", name)));
+        }
+        let mut table_names: Vec<&String> = self.tables.keys().collect();
+        table_names.sort();
+        for table_name in table_names {
+            for column in &self.tables[table_name].columns {
+                if let Some(Expression::Function { name: function, arguments }) = &column.default {
+                    if function == "NEXTVAL" && arguments.first() == Some(&Expression::Text(name.clone())) {
+                        return Err(Error::new(CATALOG_ERROR, format!("Cannot drop sequence '{}' because column '{}' of table '{}' depends on it", name, column.name, table_name)));
+                    }
+                }
+            }
+        }
+        self.sequences.borrow_mut().remove(&name);
+        return Ok(());
+    }
+
+    fn find_user_type(&self, name: &String) -> Option<(u32, &UserType)> {
+        let position = self.user_types.iter().position(|user_type| user_type.name() == name)?;
+        return Some((position as u32, &self.user_types[position]));
+    }
+
+    // Ascending sequences count up from 1 and descending ones down from -1
+    // unless other bounds are given
+    pub fn create_sequence(&mut self, name: String, start: Option<i64>, increment: i64, min: Option<i64>, max: Option<i64>) -> Result<(), Error> {
+        if self.sequences.borrow().contains_key(&name) {
+            return Err(Error::new(CATALOG_ERROR, format!("Sequence already exists: {}", name)));
+        }
+        if increment == 0 {
+            return Err(Error::new(DATA_ERROR, format!("Increment of sequence '{}' must not be zero", name)));
+        }
+        let min = min.unwrap_or(if increment > 0 { 1 } else { i64::MIN });
+        let max = max.unwrap_or(if increment > 0 { i64::MAX } else { -1 });
+        let start = start.unwrap_or(if increment > 0 { min } else { max });
+        if min > max || start < min || start > max {
+            return Err(Error::new(DATA_ERROR, format!("Start value {} of sequence '{}' is not between {} and {}", start, name, min, max)));
+        }
+        let sequence = Sequence { last_value: start, increment: increment, min: min, max: max, called: false, current: None };
+        self.sequences.borrow_mut().insert(name, sequence);
+        return Ok(());
+    }
+
+    // The sequence behind an identity column goes when the column does
+    fn drop_identity_sequence(&mut self, column: &Column) {
+        if column.identity.is_none() {
+            return;
+        }
+        if let Some(Expression::Function { arguments, .. }) = &column.default {
+            if let Some(Expression::Text(sequence_name)) = arguments.first() {
+                self.sequences.borrow_mut().remove(sequence_name);
+            }
+        }
+    }
+
+    // nextval, currval and setval, all of which take the sequence by name
+    fn evaluate_sequence_function(&self, name: &String, arguments: &Vec<Cell>) -> Result<Cell, Error> {
+        let sequence_name = match arguments.first() {
+            Some(Cell::STRING(sequence_name, _, _)) => sequence_name,
+            _ => return Err(Error::new(TYPE_ERROR, format!("Function {} expects a sequence name", name)))
+        };
+        let mut sequences = self.sequences.borrow_mut();
+        let sequence = match sequences.get_mut(sequence_name) {
+            Some(sequence) => sequence,
+            None => return Err(Error::new(CATALOG_ERROR, format!("Sequence not found: {}", sequence_name)))
+        };
+        match name.as_str() {
+            "NEXTVAL" => {
+                self.expect_arguments(name, arguments, 1)?;
+                let value = if sequence.called { sequence.last_value.checked_add(sequence.increment) } else { Some(sequence.last_value) };
+                let value = match value {
+                    Some(value) if value >= sequence.min && value <= sequence.max => value,
+                    _ if sequence.increment > 0 => return Err(Error::new(DATA_ERROR, format!("Sequence '{}' reached its maximum value ({})", sequence_name, sequence.max))),
+                    _ => return Err(Error::new(DATA_ERROR, format!("Sequence '{}' reached its minimum value ({})", sequence_name, sequence.min)))
+                };
+                sequence.last_value = value;
+                sequence.called = true;
+                sequence.current = Some(value);
+                return Ok(Cell::BIGINT(value, 0, false));
+            },
+            "CURRVAL" => {
+                self.expect_arguments(name, arguments, 1)?;
+                match sequence.current {
+                    Some(value) => Ok(Cell::BIGINT(value, 0, false)),
+                    None => Err(Error::new(DATA_ERROR, format!("Currval of sequence '{}' is not yet defined in this session", sequence_name)))
+                }
+            },
+            _ => {
+                // setval(name, value) makes the next nextval return the value
+                // after it, setval(name, value, false) the value itself
+                if arguments.len() != 2 && arguments.len() != 3 {
+                    return Err(Error::new(SYNTAX_ERROR, format!("Function {} takes 2 or 3 arguments but {} were given", name, arguments.len())));
+                }
+                let value = match arguments[1].as_i64() {
+                    Some(value) => value,
+                    None => return Err(Error::new(TYPE_ERROR, format!("Function {} expects an integer value, not {}", name, arguments[1].type_name())))
+                };
+                let called = match arguments.get(2) {
+                    Some(Cell::BOOLEAN(called, _, _)) => *called,
+                    Some(cell) => return Err(Error::new(TYPE_ERROR, format!("Function {} expects a BOOLEAN, not {}", name, cell.type_name()))),
+                    None => true
+                };
+                if value < sequence.min || value > sequence.max {
+                    return Err(Error::new(DATA_ERROR, format!("Value {} is out of bounds for sequence '{}' ({}..{})", value, sequence_name, sequence.min, sequence.max)));
+                }
+                sequence.last_value = value;
+                sequence.called = called;
+                return Ok(Cell::BIGINT(value, 0, false));
+            }
+        }
+    }
+
+    pub fn create_type(&mut self, name: String, labels: Vec<String>) -> Result<(), Error> {
+        if self.find_user_type(&name).is_some() {
+            return Err(Error::new(CATALOG_ERROR, format!("Type already exists: {}", name)));
+        }
+        for (index, label) in labels.iter().enumerate() {
+            if labels[..index].contains(label) {
+                return Err(Error::new(CATALOG_ERROR, format!("Enum label '{}' used more than once", label)));
+            }
+        }
+        self.user_types.push(UserType::Enum { name: name, labels: labels });
+        return Ok(());
+    }
+
+    pub fn create_domain(&mut self, name: String, data_type: DataType, size: u32, nullable: bool, checks: Vec<Expression>) -> Result<(), Error> {
+        if self.find_user_type(&name).is_some() {
+            return Err(Error::new(CATALOG_ERROR, format!("Type already exists: {}", name)));
+        }
+        if let DataType::USERDEFINED(type_name) = &data_type {
+            if self.find_user_type(type_name).is_none() {
+                return Err(Error::new(CATALOG_ERROR, format!("Type not found: {}", type_name)));
+            }
+        }
+        self.user_types.push(UserType::Domain { name: name, data_type: data_type, size: size, nullable: nullable, checks: checks });
+        return Ok(());
+    }
+
+    fn enum_label(&self, ordinal: u32, type_id: u32) -> &String {
+        match &self.user_types[type_id as usize] {
+            UserType::Enum { labels, .. } => &labels[ordinal as usize],
+            UserType::Domain { name, .. } => panic!("Type {} is not an enum", name)
+        }
+    }
+
+    // Renders a cell for the result printer, looking up enum labels
+    fn render_cell(&self, cell: &Cell) -> String {
+        match cell {
+            Cell::ENUM(ordinal, type_id, _, _) => format!("'{}'", self.enum_label(*ordinal, *type_id)),
+            Cell::ARRAY(elements, _, _) => {
+                let elements: Vec<String> = elements.iter().map(|element| match element {
+                    Cell::NULL => "NULL".to_string(),
+                    _ => self.render_cell(element)
+                }).collect();
+                format!("{{{}}}", elements.join(","))
+            },
+            _ => cell.render()
+        }
+    }
+
+    // Converts a value to an enum or domain type. Domain values are stored
+    // as their underlying type once the domain constraints hold.
+    fn convert_user_type(&self, cell: Cell, name: &String, size: u32, nullable: bool, column_name: Option<&String>) -> Result<Cell, Error> {
+        let (type_id, user_type) = match self.find_user_type(name) {
+            Some(user_type) => user_type,
+            None => return Err(Error::new(CATALOG_ERROR, format!("Type not found: {}", name)))
+        };
+        match user_type {
+            UserType::Enum { labels, .. } => match cell {
+                Cell::NULL => Ok(Cell::NULL),
+                Cell::ENUM(ordinal, value_type, _, _) if value_type == type_id => Ok(Cell::ENUM(ordinal, type_id, size, nullable)),
+                Cell::STRING(text, _, _) | Cell::CHAR(text, _, _) => match labels.iter().position(|label| *label == text) {
+                    Some(ordinal) => Ok(Cell::ENUM(ordinal as u32, type_id, size, nullable)),
+                    None => Err(Error::new(DATA_ERROR, format!("Invalid input value for enum {}: '{}'", name, text)))
+                },
+                _ => match column_name {
+                    Some(column_name) => Err(Error::new(TYPE_ERROR, format!("Column '{}' is of type {} but expression is of type {}", column_name, name, cell.type_name()))),
+                    None => Err(Error::new(TYPE_ERROR, format!("Cannot cast {} to {}", cell.type_name(), name)))
+                }
+            },
+            UserType::Domain { data_type, size: base_size, nullable: domain_nullable, checks, .. } => {
+                if let Cell::NULL = cell {
+                    if !domain_nullable {
+                        return Err(Error::new(CONSTRAINT_ERROR, format!("Domain {} does not allow null values", name)));
+                    }
+                }
+                let value = self.convert_cell(cell, data_type, *base_size, nullable, column_name)?;
+                let columns = vec![Box::new(Column { name: "VALUE".to_string(), data_type: data_type.clone(), size: *base_size, nullable: true, default: None, identity: None })];
+                let row = vec![value.clone()];
+                for check in checks {
+                    match self.evaluate_expression(check, &columns, &row)? {
+                        Cell::BOOLEAN(false, _, _) => {
+                            return Err(Error::new(CONSTRAINT_ERROR, format!("Value {} for domain {} violates check constraint", self.render_cell(&value), name)));
+                        },
+                        Cell::BOOLEAN(true, _, _) | Cell::NULL => {},
+                        result => return Err(Error::new(TYPE_ERROR, format!("Check constraint of domain {} must be BOOLEAN, not {}", name, result.type_name())))
+                    }
+                }
+                return Ok(value);
+            }
+        }
+    }
+
+    // Lists the tables of the current schema and the temporary tables
+    pub fn describe_tables(&mut self) {
+        let mut table_names: Vec<&String> = self.tables.keys().filter(|name| {
+            let schema = Database::split_name(name).0;
+            schema == self.current_schema || schema == TEMPORARY_SCHEMA
+        }).collect();
+        table_names.sort_by_key(|name| (Database::split_name(name).1, Database::split_name(name).0 == TEMPORARY_SCHEMA));
+        for name in table_names {
+            let table = self.tables.get(name).unwrap();
+            match Database::split_name(name) {
+                (TEMPORARY_SCHEMA, table_name) => println!("Table name: {} (temporary)", table_name),
+                (_, table_name) => println!("Table name: {}", table_name)
+            }
+            println!("\tRow count: {}", table.row_count);
+            println!("\tColumn count: {}", table.columns.len());
+        }
+    }
+
+    // Lists the columns of a table along with the constraints on each
+    pub fn describe_table(&self, name: &String) -> Result<(), Error> {
+        let name = &self.resolve_name(name);
+        let table = match self.tables.get(name) {
+            Some(table) => table,
+            None => return Err(Error::new(CATALOG_ERROR, format!("Table not found: {}", name)))
+        };
+        println!("Table: {}", name);
+        println!();
+        for heading in ["Column", "Type", "Size", "Nullable", "Default", "Constraints"] {
+            print!(" {:<15} |", heading);
+        }
+        println!();
+        for column in &table.columns {
+            let mut constraints = Vec::new();
+            match column.identity {
+                Some(Identity::Always) => constraints.push("GENERATED ALWAYS AS IDENTITY".to_string()),
+                Some(Identity::ByDefault) => constraints.push("GENERATED BY DEFAULT AS IDENTITY".to_string()),
+                None => {}
+            }
+            for index in table.indexes.iter().filter(|index| index.columns.contains(&column.name)) {
+                constraints.push(format!("{} {}", index.name, if index.primary { "PRIMARY KEY" } else { "UNIQUE" }));
+            }
+            for foreign_key in table.foreign_keys.iter().filter(|foreign_key| foreign_key.columns.contains(&column.name)) {
+                constraints.push(format!("{} FOREIGN KEY", foreign_key.name));
+            }
+            for check in table.checks.iter().filter(|check| Database::references_column(&check.expression, &column.name)) {
+                constraints.push(format!("{} CHECK", check.name));
+            }
+            let size = if column.size > 0 { column.size.to_string() } else { String::new() };
+            let default = column.default.as_ref().map_or(String::new(), |default| default.to_string());
+            print!(" {:<15} | {:<15} | {:<15} |", column.name, column.data_type.to_string(), size);
+            print!(" {:<15} | {:<15} |", if column.nullable { "YES" } else { "NO" }, default);
+            println!(" {:<15} |", constraints.join(", "));
+        }
+        println!();
+        return Ok(());
+    }
+
+    // Prints the CREATE TABLE statement that recreates a table, with every
+    // constraint written out under its name
+    pub fn show_create_table(&self, name: &String) -> Result<(), Error> {
+        let name = &self.resolve_name(name);
+        let table = match self.tables.get(name) {
+            Some(table) => table,
+            None => return Err(Error::new(CATALOG_ERROR, format!("Table not found: {}", name)))
+        };
+        let mut definitions: Vec<String> = table.columns.iter().map(|column| column.to_string()).collect();
+        for index in &table.indexes {
+            let kind = if index.primary { "PRIMARY KEY" } else { "UNIQUE" };
+            definitions.push(format!("CONSTRAINT {} {} ({})", index.name, kind, index.columns.join(", ")));
+        }
+        for foreign_key in &table.foreign_keys {
+            let mut definition = format!("CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({})", foreign_key.name, foreign_key.columns.join(", "), self.display_name(&foreign_key.table), foreign_key.references.join(", "));
+            if foreign_key.on_delete != ReferentialAction::NoAction {
+                definition.push_str(&format!(" ON DELETE {}", foreign_key.on_delete));
+            }
+            if foreign_key.on_update != ReferentialAction::NoAction {
+                definition.push_str(&format!(" ON UPDATE {}", foreign_key.on_update));
+            }
+            definitions.push(definition);
+        }
+        for check in &table.checks {
+            definitions.push(format!("CONSTRAINT {} CHECK ({})", check.name, check.expression));
+        }
+        match Database::split_name(name) {
+            (TEMPORARY_SCHEMA, table_name) => println!("CREATE TEMPORARY TABLE {} (", table_name),
+            _ => println!("CREATE TABLE {} (", self.display_name(name))
+        }
+        println!("    {}", definitions.join(",\n    "));
+        println!(");");
+        return Ok(());
+    }
+
+    // Builds an integer cell of the given numeric rank, failing when the value
+    // does not fit
+    fn make_integer(&self, value: i64, rank: u8) -> Result<Cell, Error> {
+        match rank {
+            SMALLINT_RANK => match i16::try_from(value) {
+                Ok(value) => Ok(Cell::SMALLINT(value, 0, false)),
+                Err(_) => Err(Error::new(DATA_ERROR, "SMALLINT out of range".to_string()))
+            },
+            INT_RANK => match i32::try_from(value) {
+                Ok(value) => Ok(Cell::INT(value, 0, false)),
+                Err(_) => Err(Error::new(DATA_ERROR, "INT out of range".to_string()))
+            },
+            _ => Ok(Cell::BIGINT(value, 0, false))
+        }
+    }
+
+    // Integer literals are INT or BIGINT, literals with a fraction or too
+    // large for BIGINT are exact decimals, and exponents make a DOUBLE
+    fn parse_number(&self, text: &String) -> Result<Cell, Error> {
+        if text.contains('e') || text.contains('E') {
+            return match text.parse::<f64>() {
+                Ok(value) if value.is_finite() => Ok(Cell::DOUBLE(value, 0, false)),
+                Ok(_) => Err(Error::new(DATA_ERROR, format!("Numeric literal out of range: {}", text))),
+                Err(_) => Err(Error::new(DATA_ERROR, format!("Invalid number: {}", text)))
+            };
+        }
+        if let Ok(value) = text.parse::<i64>() {
+            return match i32::try_from(value) {
+                Ok(value) => Ok(Cell::INT(value, 0, false)),
+                Err(_) => Ok(Cell::BIGINT(value, 0, false))
+            };
+        }
+        match decimal::parse_decimal(text) {
+            Some((value, scale)) if decimal::precision_of(value) <= decimal::MAX_PRECISION && scale <= decimal::MAX_PRECISION => Ok(Cell::DECIMAL(value, scale, 0, false)),
+            _ => Err(Error::new(DATA_ERROR, format!("Numeric literal out of range: {}", text)))
+        }
+    }
+
+    // Reads a string as the type of another value it is combined with.
+    // Numbers keep their own precision rather than taking the other's.
+    fn parse_text_like(&self, text: &String, other: &Cell) -> Result<Cell, Error> {
+        if other.numeric_rank().is_some() {
+            if text.trim().parse::<i64>().is_ok() {
+                return self.parse_number(&text.trim().to_string());
+            }
+            let invalid = Error::new(DATA_ERROR, format!("Invalid input syntax for type {}: '{}'", other.type_name(), text));
+            return self.parse_text(text, &DataType::DECIMAL(decimal::MAX_PRECISION, 0), &String::new()).map_err(|_| invalid);
+        }
+        if let Cell::ENUM(_, type_id, _, _) = other {
+            let data_type = DataType::USERDEFINED(self.user_types[*type_id as usize].name().clone());
+            return self.cast_cell(Cell::STRING(text.clone(), 0, false), &data_type, 0);
+        }
+        let data_type = match other {
+            Cell::BOOLEAN(_, _, _) => DataType::BOOLEAN,
+            Cell::DATE(_, _, _) => DataType::DATE,
+            Cell::TIME(_, _, _) => DataType::TIME,
+            Cell::TIMESTAMP(_, _, _) => DataType::TIMESTAMP,
+            Cell::TIMESTAMPTZ(_, _, _, _) => DataType::TIMESTAMPTZ,
+            Cell::INTERVAL(_, _, _) => DataType::INTERVAL,
+            Cell::BLOB(_, _, _) => DataType::BLOB,
+            Cell::JSON(_, _, _) => DataType::JSON,
+            Cell::UUID(_, _, _) => DataType::UUID,
+            _ => return Ok(Cell::STRING(text.clone(), 0, false))
+        };
+        return self.parse_text(text, &data_type, &String::new());
+    }
+
+    pub fn evaluate_expression(&self, expression: &Expression, columns: &Vec<Box<Column>>, row: &Vec<Cell>) -> Result<Cell, Error> {
+        match expression {
+            Expression::Column(name) => {
+                for (index, column) in columns.iter().enumerate() {
+                    if column.name == *name {
+                        return Ok(row.get(index).cloned().unwrap_or(Cell::NULL));
+                    }
+                }
+                return Err(Error::new(CATALOG_ERROR, format!("Column not found: {}", name)));
+            },
+            Expression::Number(text) => self.parse_number(text),
+            Expression::Text(text) => Ok(Cell::STRING(text.clone(), 0, false)),
+            Expression::Boolean(value) => Ok(Cell::BOOLEAN(*value, 0, false)),
+            Expression::Null => Ok(Cell::NULL),
+            Expression::Default => Err(Error::new(SYNTAX_ERROR, "DEFAULT is not allowed in this context".to_string())),
+            Expression::TypedLiteral { data_type, text } => match data_type {
+                DataType::BLOB => self.parse_hex(text),
+                _ => self.parse_datetime(text, data_type)
+            },
+            Expression::Unary { operator, operand } => {
+                let value = self.evaluate_expression(operand, columns, row)?;
+                return self.evaluate_unary(operator, value);
+            },
+            Expression::Cast { expression, data_type, size } => {
+                let value = self.evaluate_expression(expression, columns, row)?;
+                return self.cast_cell(value, data_type, *size);
+            },
+            Expression::Binary { operator, left, right } => {
+                let left = self.evaluate_expression(left, columns, row)?;
+                let right = self.evaluate_expression(right, columns, row)?;
+                return self.evaluate_binary(operator, left, right);
+            },
+            Expression::Function { name, arguments } => {
+                if self.is_aggregate(name) {
+                    return Err(Error::new(SYNTAX_ERROR, format!("Aggregate function {} is not allowed here", name)));
+                }
+                let mut values = Vec::new();
+                for argument in arguments {
+                    values.push(self.evaluate_expression(argument, columns, row)?);
+                }
+                return self.evaluate_function(name, values);
+            }
+        }
+    }
+
+    fn expect_arguments(&self, name: &String, arguments: &Vec<Cell>, count: usize) -> Result<(), Error> {
+        if arguments.len() != count {
+            return Err(Error::new(SYNTAX_ERROR, format!("Function {} takes {} arguments but {} were given", name, count, arguments.len())));
+        }
+        return Ok(());
+    }
+
+    fn evaluate_function(&self, name: &String, arguments: Vec<Cell>) -> Result<Cell, Error> {
+        match name.as_str() {
+            "NOW" | "CURRENT_TIMESTAMP" => {
+                self.expect_arguments(name, &arguments, 0)?;
+                return Ok(Cell::TIMESTAMPTZ(datetime::now_micros(), 0, 0, false));
+            },
+            "CURRENT_DATE" => {
+                self.expect_arguments(name, &arguments, 0)?;
+                return Ok(Cell::DATE(datetime::now_micros().div_euclid(MICROS_PER_DAY) as i32, 0, false));
+            },
+            "LENGTH" | "OCTET_LENGTH" => {
+                self.expect_arguments(name, &arguments, 1)?;
+                match &arguments[0] {
+                    Cell::NULL => Ok(Cell::NULL),
+                    Cell::BLOB(value, _, _) => Ok(Cell::INT(value.len() as i32, 0, false)),
+                    // LENGTH counts characters, OCTET_LENGTH counts bytes
+                    Cell::STRING(value, _, _) if name == "LENGTH" => Ok(Cell::INT(value.chars().count() as i32, 0, false)),
+                    Cell::STRING(value, _, _) => Ok(Cell::INT(value.len() as i32, 0, false)),
+                    Cell::CHAR(value, _, _) if name == "LENGTH" => Ok(Cell::INT(value.trim_end_matches(' ').chars().count() as i32, 0, false)),
+                    Cell::CHAR(value, _, _) => Ok(Cell::INT(value.len() as i32, 0, false)),
+                    _ => Err(Error::new(TYPE_ERROR, format!("Function {} is not defined for {}", name, arguments[0].type_name())))
+                }
+            },
+            "JSON_EXTRACT" => {
+                self.expect_arguments(name, &arguments, 2)?;
+                let document = match self.as_json(name, &arguments[0])? {
+                    Some(document) => document,
+                    None => return Ok(Cell::NULL)
+                };
+                let path = match &arguments[1] {
+                    Cell::NULL => return Ok(Cell::NULL),
+                    Cell::STRING(path, _, _) => match json::parse_path(path) {
+                        Some(path) => path,
+                        None => return Err(Error::new(DATA_ERROR, format!("Invalid JSON path: '{}'", path)))
+                    },
+                    _ => return Err(Error::new(TYPE_ERROR, format!("Function JSON_EXTRACT expects a path but got {}", arguments[1].type_name())))
+                };
+                match document.get_path(&path) {
+                    Some(value) => Ok(Cell::JSON(value.clone(), 0, false)),
+                    None => Ok(Cell::NULL)
+                }
+            },
+            "JSON_ARRAY_LENGTH" | "JSON_OBJECT_LENGTH" => {
+                self.expect_arguments(name, &arguments, 1)?;
+                let document = match self.as_json(name, &arguments[0])? {
+                    Some(document) => document,
+                    None => return Ok(Cell::NULL)
+                };
+                match (name.as_str(), &document) {
+                    ("JSON_ARRAY_LENGTH", JsonValue::Array(elements)) => Ok(Cell::INT(elements.len() as i32, 0, false)),
+                    ("JSON_OBJECT_LENGTH", JsonValue::Object(members)) => Ok(Cell::INT(members.len() as i32, 0, false)),
+                    _ => Err(Error::new(DATA_ERROR, format!("Function {} is not defined for a JSON {}", name, document.type_name())))
+                }
+            },
+            "ARRAY" => self.make_array(arguments),
+            "NEXTVAL" | "CURRVAL" | "SETVAL" => self.evaluate_sequence_function(name, &arguments),
+            "GEN_RANDOM_UUID" => {
+                self.expect_arguments(name, &arguments, 0)?;
+                return Ok(Cell::UUID(uuid::random_uuid(), 0, false));
+            },
+            "ARRAY_LENGTH" | "CARDINALITY" => {
+                self.expect_arguments(name, &arguments, if name == "ARRAY_LENGTH" { 2 } else { 1 })?;
+                let elements = match &arguments[0] {
+                    Cell::NULL => return Ok(Cell::NULL),
+                    Cell::ARRAY(elements, _, _) => elements,
+                    _ => return Err(Error::new(TYPE_ERROR, format!("Function {} is not defined for {}", name, arguments[0].type_name())))
+                };
+                // Arrays have a single dimension, and an empty one has no
+                // length in it
+                if name == "ARRAY_LENGTH" && (arguments[1].as_i64() != Some(1) || elements.is_empty()) {
+                    return Ok(Cell::NULL);
+                }
+                return Ok(Cell::INT(elements.len() as i32, 0, false));
+            },
+            "EXTRACT" => {
+                self.expect_arguments(name, &arguments, 2)?;
+                return self.extract_field(&arguments[0], &arguments[1]);
+            },
+            "DATE_TRUNC" => {
+                self.expect_arguments(name, &arguments, 2)?;
+                let field = match &arguments[0] {
+                    Cell::STRING(field, _, _) => field.to_uppercase(),
+                    _ => return Err(Error::new(TYPE_ERROR, format!("Function DATE_TRUNC expects a field name but got {}", arguments[0].type_name())))
+                };
+                let invalid = Error::new(DATA_ERROR, format!("Unsupported DATE_TRUNC field: {}", field));
+                let value = match &arguments[1] {
+                    Cell::STRING(text, _, _) => self.parse_datetime(text, &DataType::TIMESTAMP)?,
+                    value => value.clone()
+                };
+                match value {
+                    Cell::NULL => Ok(Cell::NULL),
+                    Cell::DATE(value, _, _) => match datetime::truncate(&field, value as i64 * MICROS_PER_DAY) {
+                        Some(value) => Ok(Cell::TIMESTAMP(value, 0, false)),
+                        None => Err(invalid)
+                    },
+                    Cell::TIMESTAMP(value, _, _) => match datetime::truncate(&field, value) {
+                        Some(value) => Ok(Cell::TIMESTAMP(value, 0, false)),
+                        None => Err(invalid)
+                    },
+                    Cell::TIMESTAMPTZ(value, offset, _, _) => {
+                        // Truncate the local time the value is displayed in
+                        let shift = offset as i64 * MICROS_PER_SECOND;
+                        match datetime::truncate(&field, value + shift) {
+                            Some(value) => Ok(Cell::TIMESTAMPTZ(value - shift, offset, 0, false)),
+                            None => Err(invalid)
+                        }
+                    },
+                    _ => Err(Error::new(TYPE_ERROR, format!("Function DATE_TRUNC is not defined for {}", arguments[1].type_name())))
+                }
+            },
+            _ => Err(Error::new(CATALOG_ERROR, format!("Function not found: {}({} arguments)", name, arguments.len())))
+        }
+    }
+
+    // The elements of an array literal share one type. Numbers are widened
+    // to the widest type among them, strings go together, and other values
+    // need the same type.
+    fn make_array(&self, elements: Vec<Cell>) -> Result<Cell, Error> {
+        let category = |cell: &Cell| match cell {
+            _ if cell.numeric_rank().is_some() => DataType::DOUBLE,
+            Cell::STRING(_, _, _) | Cell::CHAR(_, _, _) => DataType::TEXT,
+            _ => self.data_type_of(cell)
+        };
+        let values: Vec<&Cell> = elements.iter().filter(|element| !matches!(element, Cell::NULL)).collect();
+        for pair in values.windows(2) {
+            if category(pair[0]) != category(pair[1]) {
+                return Err(Error::new(TYPE_ERROR, format!("ARRAY elements of types {} and {} cannot be matched", pair[0].type_name(), pair[1].type_name())));
+            }
+        }
+        let rank = match values.iter().filter_map(|value| value.numeric_rank()).max() {
+            Some(rank) => rank,
+            None => return Ok(Cell::ARRAY(elements, 0, false))
+        };
+        let scale = values.iter().filter_map(|value| value.as_decimal()).map(|(_, scale)| scale).max().unwrap_or(0);
+        let mut widened = Vec::new();
+        for element in elements {
+            widened.push(match element {
+                Cell::NULL => Cell::NULL,
+                _ if rank == DOUBLE_RANK => Cell::DOUBLE(element.as_f64().unwrap(), 0, false),
+                _ if rank == DECIMAL_RANK => {
+                    let (value, value_scale) = element.as_decimal().unwrap();
+                    match decimal::rescale(value, value_scale, scale) {
+                        Some(value) if decimal::precision_of(value) <= decimal::MAX_PRECISION => Cell::DECIMAL(value, scale, 0, false),
+                        _ => return Err(Error::new(DATA_ERROR, "DECIMAL out of range".to_string()))
+                    }
+                },
+                _ => self.make_integer(element.as_i64().unwrap(), rank)?
+            });
+        }
+        return Ok(Cell::ARRAY(widened, 0, false));
+    }
+
+    // JSON functions also accept documents written as strings
+    fn as_json(&self, name: &String, cell: &Cell) -> Result<Option<JsonValue>, Error> {
+        match cell {
+            Cell::NULL => Ok(None),
+            Cell::JSON(value, _, _) => Ok(Some(value.clone())),
+            Cell::STRING(text, _, _) => match json::parse_json(text) {
+                Ok(value) => Ok(Some(value)),
+                Err(message) => Err(Error::new(DATA_ERROR, format!("Invalid JSON: {}", message)))
+            },
+            _ => Err(Error::new(TYPE_ERROR, format!("Function {} is not defined for {}", name, cell.type_name())))
+        }
+    }
+
+    // Implements document -> key and document ->> key. Integer keys index
+    // into arrays; ->> returns the value as text.
+    fn evaluate_json_access(&self, operator: &String, left: Cell, right: Cell) -> Result<Cell, Error> {
+        let document = match left {
+            Cell::NULL => return Ok(Cell::NULL),
+            Cell::JSON(value, _, _) => value,
+            _ => return Err(Error::new(TYPE_ERROR, format!("Operator {} is not defined for {}", operator, left.type_name())))
+        };
+        let value = match &right {
+            Cell::NULL => return Ok(Cell::NULL),
+            Cell::STRING(key, _, _) => document.get_key(key),
+            _ => match right.as_i64() {
+                Some(index) => document.get_index(index),
+                None => return Err(Error::new(TYPE_ERROR, format!("Operator {} expects a key or an index but got {}", operator, right.type_name())))
+            }
+        };
+        match (value, operator.as_str()) {
+            (None, _) => Ok(Cell::NULL),
+            (Some(value), "->") => Ok(Cell::JSON(value.clone(), 0, false)),
+            (Some(JsonValue::Null), _) => Ok(Cell::NULL),
+            (Some(JsonValue::String(text)), _) => Ok(Cell::STRING(text.clone(), 0, false)),
+            (Some(value), _) => Ok(Cell::STRING(json::format_json(value), 0, false))
+        }
+    }
+
+    // Arrays are indexed from 1, and indexes outside the array give NULL
+    fn evaluate_subscript(&self, array: Cell, index: Cell) -> Result<Cell, Error> {
+        let elements = match array {
+            Cell::NULL => return Ok(Cell::NULL),
+            Cell::ARRAY(elements, _, _) => elements,
+            _ => return Err(Error::new(TYPE_ERROR, format!("Cannot subscript {}", array.type_name())))
+        };
+        let index = match index {
+            Cell::NULL => return Ok(Cell::NULL),
+            _ if index.numeric_rank().map_or(false, |rank| rank <= BIGINT_RANK) => index.as_i64().unwrap(),
+            _ => return Err(Error::new(TYPE_ERROR, format!("Array subscript must be an integer, not {}", index.type_name())))
+        };
+        if index < 1 || index as usize > elements.len() {
+            return Ok(Cell::NULL);
+        }
+        return Ok(elements[index as usize - 1].clone());
+    }
+
+    // Implements value = ANY (array) and value = ALL (array). NULL elements
+    // make the result unknown unless another element decides it.
+    fn evaluate_quantified(&self, operator: &String, value: Cell, array: Cell) -> Result<Cell, Error> {
+        let (comparison, quantifier) = operator.split_once(' ').unwrap();
+        let elements = match array {
+            Cell::NULL => return Ok(Cell::NULL),
+            Cell::ARRAY(elements, _, _) => elements,
+            _ => return Err(Error::new(TYPE_ERROR, format!("Operator {} expects an array but got {}", operator, array.type_name())))
+        };
+        let deciding = quantifier == "ANY";
+        let mut unknown = false;
+        for element in elements {
+            match self.evaluate_binary(&comparison.to_string(), value.clone(), element)? {
+                Cell::BOOLEAN(result, _, _) if result == deciding => return Ok(Cell::BOOLEAN(deciding, 0, false)),
+                Cell::NULL => unknown = true,
+                _ => {}
+            }
+        }
+        if unknown {
+            return Ok(Cell::NULL);
+        }
+        return Ok(Cell::BOOLEAN(!deciding, 0, false));
+    }
+
+    fn extract_field(&self, field: &Cell, value: &Cell) -> Result<Cell, Error> {
+        let field = match field {
+            Cell::STRING(field, _, _) => field.clone(),
+            _ => return Err(Error::new(TYPE_ERROR, "EXTRACT expects a field name".to_string()))
+        };
+        let invalid = Error::new(DATA_ERROR, format!("Unsupported EXTRACT field {} for {}", field, value.type_name()));
+        // Wall clock time of the value and the offset it is displayed in
+        let (micros, offset) = match value {
+            Cell::NULL => return Ok(Cell::NULL),
+            Cell::STRING(text, _, _) => return self.extract_field(&Cell::STRING(field, 0, false), &self.parse_datetime(text, &DataType::TIMESTAMP)?),
+            Cell::DATE(value, _, _) => (*value as i64 * MICROS_PER_DAY, 0),
+            Cell::TIME(value, _, _) => (*value, 0),
+            Cell::TIMESTAMP(value, _, _) => (*value, 0),
+            Cell::TIMESTAMPTZ(value, offset, _, _) => (value + *offset as i64 * MICROS_PER_SECOND, *offset),
+            Cell::INTERVAL(interval, _, _) => {
+                return match field.as_str() {
+                    "YEAR" => Ok(Cell::INT(interval.months / 12, 0, false)),
+                    "MONTH" => Ok(Cell::INT(interval.months % 12, 0, false)),
+                    "DAY" => Ok(Cell::INT(interval.days, 0, false)),
+                    "HOUR" => Ok(Cell::BIGINT(interval.micros / datetime::MICROS_PER_HOUR, 0, false)),
+                    "MINUTE" => Ok(Cell::INT((interval.micros / datetime::MICROS_PER_MINUTE % 60) as i32, 0, false)),
+                    "SECOND" => Ok(Cell::DECIMAL((interval.micros % datetime::MICROS_PER_MINUTE) as i128, 6, 0, false)),
+                    "EPOCH" => Ok(Cell::DECIMAL(interval.total_micros(), 6, 0, false)),
+                    _ => Err(invalid)
+                };
+            },
+            _ => return Err(Error::new(TYPE_ERROR, format!("EXTRACT is not defined for {}", value.type_name())))
+        };
+        if let Cell::TIME(_, _, _) = value {
+            if !["HOUR", "MINUTE", "SECOND", "EPOCH"].contains(&field.as_str()) {
+                return Err(invalid);
+            }
+        }
+        match field.as_str() {
+            "SECOND" => Ok(Cell::DECIMAL((micros.rem_euclid(datetime::MICROS_PER_MINUTE)) as i128, 6, 0, false)),
+            "EPOCH" => Ok(Cell::DECIMAL((micros - offset as i64 * MICROS_PER_SECOND) as i128, 6, 0, false)),
+            "TIMEZONE" => Ok(Cell::INT(offset, 0, false)),
+            _ => match datetime::extract(&field, micros) {
+                Some(value) => self.make_integer(value, INT_RANK),
+                None => Err(invalid)
+            }
+        }
+    }
+
+    fn parse_hex(&self, text: &String) -> Result<Cell, Error> {
+        let invalid = Error::new(DATA_ERROR, format!("Invalid hexadecimal literal: X'{}'", text));
+        if text.len() % 2 != 0 {
+            return Err(invalid);
+        }
+        let mut bytes = Vec::new();
+        for i in (0..text.len()).step_by(2) {
+            match text.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()) {
+                Some(byte) => bytes.push(byte),
+                None => return Err(invalid)
+            }
+        }
+        return Ok(Cell::BLOB(bytes, 0, false));
+    }
+
+    // Reads a date or time value from its ISO-8601 text form
+    fn parse_datetime(&self, text: &String, data_type: &DataType) -> Result<Cell, Error> {
+        let invalid = Error::new(DATA_ERROR, format!("Invalid {} value: '{}'", data_type, text));
+        let cell = match data_type {
+            DataType::DATE => datetime::parse_timestamp(text).map(|(micros, _)| Cell::DATE(micros.div_euclid(MICROS_PER_DAY) as i32, 0, false)),
+            DataType::TIME => datetime::parse_time(text.trim()).map(|micros| Cell::TIME(micros, 0, false)),
+            // Like the SQL standard, a time zone given for a timestamp
+            // without time zone is ignored
+            DataType::TIMESTAMP => datetime::parse_timestamp(text).map(|(micros, _)| Cell::TIMESTAMP(micros, 0, false)),
+            DataType::TIMESTAMPTZ => datetime::parse_timestamp(text).map(|(micros, offset)| {
+                let offset = offset.unwrap_or(0);
+                Cell::TIMESTAMPTZ(micros - offset as i64 * MICROS_PER_SECOND, offset, 0, false)
+            }),
+            DataType::INTERVAL => datetime::parse_interval(text).map(|interval| Cell::INTERVAL(interval, 0, false)),
+            _ => None
+        };
+        return cell.ok_or(invalid);
+    }
+
+    fn is_aggregate(&self, name: &String) -> bool {
+        return name == "SUM" || name == "AVG";
+    }
+
+    // Whether an expression can give a different value each time it is
+    // evaluated within a statement. now() is not, as in PostgreSQL.
+    fn is_volatile(&self, expression: &Expression) -> bool {
+        match expression {
+            Expression::Function { name, arguments } => {
+                name == "GEN_RANDOM_UUID" || name == "NEXTVAL" || name == "SETVAL" || arguments.iter().any(|argument| self.is_volatile(argument))
+            },
+            Expression::Unary { operand, .. } => self.is_volatile(operand),
+            Expression::Cast { expression, .. } => self.is_volatile(expression),
+            Expression::Binary { left, right, .. } => self.is_volatile(left) || self.is_volatile(right),
+            _ => false
+        }
+    }
+
+    fn references_column(expression: &Expression, column: &String) -> bool {
+        match expression {
+            Expression::Column(name) => name == column,
+            Expression::Function { arguments, .. } => arguments.iter().any(|argument| Database::references_column(argument, column)),
+            Expression::Unary { operand, .. } => Database::references_column(operand, column),
+            Expression::Cast { expression, .. } => Database::references_column(expression, column),
+            Expression::Binary { left, right, .. } => Database::references_column(left, column) || Database::references_column(right, column),
+            _ => false
+        }
+    }
+
+    fn rename_column_reference(expression: &mut Expression, from: &String, to: &String) {
+        match expression {
+            Expression::Column(name) => {
+                if name == from {
+                    *name = to.clone();
+                }
+            },
+            Expression::Function { arguments, .. } => {
+                for argument in arguments {
+                    Database::rename_column_reference(argument, from, to);
+                }
+            },
+            Expression::Unary { operand, .. } => Database::rename_column_reference(operand, from, to),
+            Expression::Cast { expression, .. } => Database::rename_column_reference(expression, from, to),
+            Expression::Binary { left, right, .. } => {
+                Database::rename_column_reference(left, from, to);
+                Database::rename_column_reference(right, from, to);
+            },
+            _ => {}
+        }
+    }
+
+    fn contains_aggregate(&self, expression: &Expression) -> bool {
+        match expression {
+            Expression::Function { name, arguments } => {
+                self.is_aggregate(name) || arguments.iter().any(|argument| self.contains_aggregate(argument))
+            },
+            Expression::Unary { operand, .. } => self.contains_aggregate(operand),
+            Expression::Cast { expression, .. } => self.contains_aggregate(expression),
+            Expression::Binary { left, right, .. } => self.contains_aggregate(left) || self.contains_aggregate(right),
+            _ => false
+        }
+    }
+
+    // Evaluates an expression over all rows of a table, producing a single
+    // value. Column references are only allowed inside aggregate functions.
+    fn evaluate_aggregate(&self, expression: &Expression, table: &Table) -> Result<Cell, Error> {
+        match expression {
+            Expression::Function { name, arguments } if self.is_aggregate(name) => {
+                if arguments.len() != 1 {
+                    return Err(Error::new(SYNTAX_ERROR, format!("Function {} takes exactly one argument", name)));
+                }
+                let mut sum = Cell::NULL;
+                let mut count: i64 = 0;
+                for row in &table.rows {
+                    let value = self.evaluate_expression(&arguments[0], &table.columns, &table.read_row(row))?;
+                    if let Cell::NULL = value {
+                        continue;
+                    }
+                    // Sum integers in a wider type so that the total cannot
+                    // overflow the argument type
+                    let value = match value {
+                        Cell::SMALLINT(value, _, _) => Cell::BIGINT(value as i64, 0, false),
+                        Cell::INT(value, _, _) => Cell::BIGINT(value as i64, 0, false),
+                        Cell::BIGINT(value, _, _) => Cell::DECIMAL(value as i128, 0, 0, false),
+                        Cell::DECIMAL(_, _, _, _) | Cell::DOUBLE(_, _, _) => value,
+                        _ => return Err(Error::new(TYPE_ERROR, format!("Function {} is not defined for {}", name, value.type_name())))
+                    };
+                    sum = match sum {
+                        Cell::NULL => value,
+                        _ => self.evaluate_arithmetic(&"+".to_string(), sum, value)?
+                    };
+                    count += 1;
+                }
+                if name == "AVG" && count > 0 {
+                    sum = match sum {
+                        Cell::DOUBLE(value, _, _) => Cell::DOUBLE(value / count as f64, 0, false),
+                        _ => self.evaluate_decimal_arithmetic(&"/".to_string(), sum.as_decimal().unwrap(), (count as i128, 0))?
+                    };
+                }
+                return Ok(sum);
+            },
+            Expression::Column(name) => {
+                return Err(Error::new(SYNTAX_ERROR, format!("Column '{}' must be used in an aggregate function", name)));
+            },
+            Expression::Unary { operator, operand } => {
+                let value = self.evaluate_aggregate(operand, table)?;
+                return self.evaluate_unary(operator, value);
+            },
+            Expression::Cast { expression, data_type, size } => {
+                let value = self.evaluate_aggregate(expression, table)?;
+                return self.cast_cell(value, data_type, *size);
+            },
+            Expression::Binary { operator, left, right } => {
+                let left = self.evaluate_aggregate(left, table)?;
+                let right = self.evaluate_aggregate(right, table)?;
+                return self.evaluate_binary(operator, left, right);
+            },
+            Expression::Function { name, arguments } => {
+                let mut values = Vec::new();
+                for argument in arguments {
+                    values.push(self.evaluate_aggregate(argument, table)?);
+                }
+                return self.evaluate_function(name, values);
+            },
+            _ => {
+                return self.evaluate_expression(expression, &Vec::new(), &Vec::new());
+            }
+        }
+    }
+
+    fn evaluate_unary(&self, operator: &String, value: Cell) -> Result<Cell, Error> {
+        match operator.as_str() {
+            "IS NULL" => Ok(Cell::BOOLEAN(matches!(value, Cell::NULL), 0, false)),
+            "IS NOT NULL" => Ok(Cell::BOOLEAN(!matches!(value, Cell::NULL), 0, false)),
+            "NOT" => match value {
+                Cell::NULL => Ok(Cell::NULL),
+                Cell::BOOLEAN(value, _, _) => Ok(Cell::BOOLEAN(!value, 0, false)),
+                _ => Err(Error::new(TYPE_ERROR, format!("Operator NOT is not defined for {}", value.type_name())))
+            },
+            _ => match value {
+                Cell::NULL => Ok(Cell::NULL),
+                Cell::DOUBLE(value, _, _) => Ok(Cell::DOUBLE(-value, 0, false)),
+                Cell::DECIMAL(value, scale, _, _) => Ok(Cell::DECIMAL(-value, scale, 0, false)),
+                Cell::INTERVAL(value, _, _) => Ok(Cell::INTERVAL(datetime::negate_interval(&value), 0, false)),
+                _ => match value.as_i64() {
+                    Some(integer) => match integer.checked_neg() {
+                        Some(negated) => self.make_integer(negated, value.numeric_rank().unwrap()),
+                        None => Err(Error::new(DATA_ERROR, format!("{} out of range", value.type_name())))
+                    },
+                    None => Err(Error::new(TYPE_ERROR, format!("Operator - is not defined for {}", value.type_name())))
+                }
+            }
+        }
+    }
+
+    fn evaluate_binary(&self, operator: &String, left: Cell, right: Cell) -> Result<Cell, Error> {
+        match operator.as_str() {
+            "AND" | "OR" => {
+                let left = match left {
+                    Cell::NULL => None,
+                    Cell::BOOLEAN(value, _, _) => Some(value),
+                    _ => return Err(Error::new(TYPE_ERROR, format!("Operator {} is not defined for {}", operator, left.type_name())))
+                };
+                let right = match right {
+                    Cell::NULL => None,
+                    Cell::BOOLEAN(value, _, _) => Some(value),
+                    _ => return Err(Error::new(TYPE_ERROR, format!("Operator {} is not defined for {}", operator, right.type_name())))
+                };
+                // Three-valued logic: an unknown operand only matters when
+                // the other one does not already decide the result
+                let result = if operator == "AND" {
+                    match (left, right) {
+                        (Some(false), _) | (_, Some(false)) => Some(false),
+                        (Some(true), Some(true)) => Some(true),
+                        _ => None
+                    }
+                } else {
+                    match (left, right) {
+                        (Some(true), _) | (_, Some(true)) => Some(true),
+                        (Some(false), Some(false)) => Some(false),
+                        _ => None
+                    }
+                };
+                return match result {
+                    Some(value) => Ok(Cell::BOOLEAN(value, 0, false)),
+                    None => Ok(Cell::NULL)
+                };
+            },
+            "=" | "<>" | "!=" | "<" | "<=" | ">" | ">=" => {
+                let ordering = match self.compare_cells(&left, &right)? {
+                    Some(ordering) => ordering,
+                    None => return Ok(Cell::NULL)
+                };
+                let result = match operator.as_str() {
+                    "=" => ordering == Ordering::Equal,
+                    "<>" | "!=" => ordering != Ordering::Equal,
+                    "<" => ordering == Ordering::Less,
+                    "<=" => ordering != Ordering::Greater,
+                    ">" => ordering == Ordering::Greater,
+                    _ => ordering != Ordering::Less
+                };
+                return Ok(Cell::BOOLEAN(result, 0, false));
+            },
+            "->" | "->>" => {
+                return self.evaluate_json_access(operator, left, right);
+            },
+            "[]" => {
+                return self.evaluate_subscript(left, right);
+            },
+            _ if operator.ends_with(" ANY") || operator.ends_with(" ALL") => {
+                return self.evaluate_quantified(operator, left, right);
+            },
+            _ => {
+                return self.evaluate_arithmetic(operator, left, right);
+            }
+        }
+    }
+
+    fn evaluate_arithmetic(&self, operator: &String, left: Cell, right: Cell) -> Result<Cell, Error> {
+        if matches!(left, Cell::NULL) || matches!(right, Cell::NULL) {
+            return Ok(Cell::NULL);
+        }
+        if let (Cell::STRING(text, _, _), Some(_)) = (&left, right.numeric_rank()) {
+            let left = self.parse_text_like(text, &right)?;
+            return self.evaluate_arithmetic(operator, left, right);
+        }
+        if let (Some(_), Cell::STRING(text, _, _)) = (left.numeric_rank(), &right) {
+            let right = self.parse_text_like(text, &left)?;
+            return self.evaluate_arithmetic(operator, left, right);
+        }
+        if left.is_datetime() || right.is_datetime() {
+            return self.evaluate_datetime_arithmetic(operator, left, right);
+        }
+        if left.numeric_rank().is_none() || right.numeric_rank().is_none() {
+            return Err(Error::new(TYPE_ERROR, format!("Operator {} is not defined for {} and {}", operator, left.type_name(), right.type_name())));
+        }
+        let rank = left.numeric_rank().max(right.numeric_rank()).unwrap();
+        if rank == DECIMAL_RANK {
+            return self.evaluate_decimal_arithmetic(operator, left.as_decimal().unwrap(), right.as_decimal().unwrap());
+        }
+        if rank == DOUBLE_RANK {
+            let value1 = left.as_f64().unwrap();
+            let value2 = right.as_f64().unwrap();
+            if (operator == "/" || operator == "%") && value2 == 0.0 {
+                return Err(Error::new(DATA_ERROR, "Division by zero".to_string()));
+            }
+            let result = match operator.as_str() {
+                "+" => value1 + value2,
+                "-" => value1 - value2,
+                "*" => value1 * value2,
+                "/" => value1 / value2,
+                _ => value1 % value2
+            };
+            if !result.is_finite() {
+                return Err(Error::new(DATA_ERROR, "DOUBLE out of range".to_string()));
+            }
+            return Ok(Cell::DOUBLE(result, 0, false));
+        }
+        let value1 = left.as_i64().unwrap();
+        let value2 = right.as_i64().unwrap();
+        if (operator == "/" || operator == "%") && value2 == 0 {
+            return Err(Error::new(DATA_ERROR, "Division by zero".to_string()));
+        }
+        let result = match operator.as_str() {
+            "+" => value1.checked_add(value2),
+            "-" => value1.checked_sub(value2),
+            "*" => value1.checked_mul(value2),
+            "/" => value1.checked_div(value2),
+            _ => value1.checked_rem(value2)
+        };
+        match result {
+            Some(result) => self.make_integer(result, rank),
+            None => Err(Error::new(DATA_ERROR, "BIGINT out of range".to_string()))
+        }
+    }
+
+    fn evaluate_datetime_arithmetic(&self, operator: &String, left: Cell, right: Cell) -> Result<Cell, Error> {
+        let undefined = Error::new(TYPE_ERROR, format!("Operator {} is not defined for {} and {}", operator, left.type_name(), right.type_name()));
+        let out_of_range = || Error::new(DATA_ERROR, "Date or time out of range".to_string());
+        // Put the date or time operand first for commutative operators
+        let swap = match (operator.as_str(), &left, &right) {
+            ("+", _, _) | ("*", _, _) if left.numeric_rank().is_some() => true,
+            ("+", Cell::INTERVAL(_, _, _), _) => !matches!(right, Cell::INTERVAL(_, _, _)),
+            ("+", Cell::TIME(_, _, _), Cell::DATE(_, _, _)) => true,
+            _ => false
+        };
+        let (left, right) = if swap { (right, left) } else { (left, right) };
+        let is_integer = right.numeric_rank().map_or(false, |rank| rank <= BIGINT_RANK);
+        match (operator.as_str(), &left, &right) {
+            ("+", Cell::DATE(days, _, _), _) | ("-", Cell::DATE(days, _, _), _) if is_integer => {
+                let offset = right.as_i64().unwrap();
+                let offset = if operator == "+" { Some(offset) } else { offset.checked_neg() };
+                match offset.and_then(|offset| (*days as i64).checked_add(offset)) {
+                    Some(days) if days.abs() <= datetime::MAX_DATE_DAYS => Ok(Cell::DATE(days as i32, 0, false)),
+                    _ => Err(out_of_range())
+                }
+            },
+            ("-", Cell::DATE(days1, _, _), Cell::DATE(days2, _, _)) => Ok(Cell::INT(days1 - days2, 0, false)),
+            ("+", Cell::DATE(days, _, _), Cell::TIME(time, _, _)) => Ok(Cell::TIMESTAMP(*days as i64 * MICROS_PER_DAY + time, 0, false)),
+            ("+", _, Cell::INTERVAL(interval, _, _)) | ("-", _, Cell::INTERVAL(interval, _, _)) if !matches!(left, Cell::INTERVAL(_, _, _)) => {
+                let interval = if operator == "+" { *interval } else { datetime::negate_interval(interval) };
+                match left {
+                    Cell::DATE(days, _, _) => {
+                        let value = datetime::add_interval(days as i64 * MICROS_PER_DAY, &interval).ok_or_else(out_of_range)?;
+                        Ok(Cell::TIMESTAMP(value, 0, false))
+                    },
+                    Cell::TIMESTAMP(value, _, _) => {
+                        let value = datetime::add_interval(value, &interval).ok_or_else(out_of_range)?;
+                        Ok(Cell::TIMESTAMP(value, 0, false))
+                    },
+                    Cell::TIMESTAMPTZ(value, offset, _, _) => {
+                        // Calendar arithmetic happens in the local time of the value
+                        let shift = offset as i64 * MICROS_PER_SECOND;
+                        let value = datetime::add_interval(value + shift, &interval).ok_or_else(out_of_range)?;
+                        Ok(Cell::TIMESTAMPTZ(value - shift, offset, 0, false))
+                    },
+                    Cell::TIME(value, _, _) => Ok(Cell::TIME((value + interval.micros).rem_euclid(MICROS_PER_DAY), 0, false)),
+                    _ => Err(undefined)
+                }
+            },
+            ("-", Cell::TIME(time1, _, _), Cell::TIME(time2, _, _)) => Ok(Cell::INTERVAL(Interval { months: 0, days: 0, micros: time1 - time2 }, 0, false)),
+            ("-", _, _) if left.as_timestamp().is_some() && right.as_timestamp().is_some() => {
+                let difference = left.as_timestamp().unwrap().checked_sub(right.as_timestamp().unwrap()).ok_or_else(out_of_range)?;
+                Ok(Cell::INTERVAL(datetime::interval_between(difference), 0, false))
+            },
+            ("+", Cell::INTERVAL(interval1, _, _), Cell::INTERVAL(interval2, _, _)) | ("-", Cell::INTERVAL(interval1, _, _), Cell::INTERVAL(interval2, _, _)) => {
+                let interval2 = if operator == "+" { *interval2 } else { datetime::negate_interval(interval2) };
+                let interval = Interval {
+                    months: interval1.months.checked_add(interval2.months).ok_or_else(out_of_range)?,
+                    days: interval1.days.checked_add(interval2.days).ok_or_else(out_of_range)?,
+                    micros: interval1.micros.checked_add(interval2.micros).ok_or_else(out_of_range)?
+                };
+                Ok(Cell::INTERVAL(interval, 0, false))
+            },
+            ("*", Cell::INTERVAL(interval, _, _), _) | ("/", Cell::INTERVAL(interval, _, _), _) if right.numeric_rank().is_some() => {
+                let mut factor = right.as_f64().unwrap();
+                if operator == "/" {
+                    if factor == 0.0 {
+                        return Err(Error::new(DATA_ERROR, "Division by zero".to_string()));
+                    }
+                    factor = 1.0 / factor;
+                }
+                Ok(Cell::INTERVAL(datetime::scale_interval(interval, factor), 0, false))
+            },
+            _ => Err(undefined)
+        }
+    }
+
+    fn evaluate_decimal_arithmetic(&self, operator: &String, left: (i128, u32), right: (i128, u32)) -> Result<Cell, Error> {
+        let (value1, scale1) = left;
+        let (value2, scale2) = right;
+        let out_of_range = || Error::new(DATA_ERROR, "DECIMAL out of range".to_string());
+        if (operator == "/" || operator == "%") && value2 == 0 {
+            return Err(Error::new(DATA_ERROR, "Division by zero".to_string()));
+        }
+        let (result, scale) = match operator.as_str() {
+            "*" => (value1.checked_mul(value2).ok_or_else(out_of_range)?, scale1 + scale2),
+            "/" => {
+                // Keep at least six fractional digits in quotients
+                let scale = scale1.max(scale2).max(6);
+                let dividend = decimal::rescale(value1, scale1, scale + scale2 + 1).ok_or_else(out_of_range)?;
+                (decimal::rescale(dividend / value2, scale + 1, scale).unwrap(), scale)
+            },
+            _ => {
+                let scale = scale1.max(scale2);
+                let value1 = decimal::rescale(value1, scale1, scale).ok_or_else(out_of_range)?;
+                let value2 = decimal::rescale(value2, scale2, scale).ok_or_else(out_of_range)?;
+                let result = match operator.as_str() {
+                    "+" => value1.checked_add(value2),
+                    "-" => value1.checked_sub(value2),
+                    _ => value1.checked_rem(value2)
+                };
+                (result.ok_or_else(out_of_range)?, scale)
+            }
+        };
+        if decimal::precision_of(result) > decimal::MAX_PRECISION {
+            return Err(out_of_range());
+        }
+        return Ok(Cell::DECIMAL(result, scale, 0, false));
+    }
+
+    // Converts a value to the data type of the column it is stored in
+    // Assignment conversion, used by INSERT. See the coercion matrix in
+    // support.md.
+    fn coerce_to_column(&self, cell: Cell, column: &Column) -> Result<Cell, Error> {
+        return self.convert_cell(cell, &column.data_type, column.size, column.nullable, Some(&column.name));
+    }
+
+    // Explicit conversion, used by CAST(expression AS type) and expression::type
+    fn cast_cell(&self, cell: Cell, data_type: &DataType, size: u32) -> Result<Cell, Error> {
+        return self.convert_cell(cell, data_type, size, false, None);
+    }
+
+    // Converts a value to a type. Assignments to a column only perform the
+    // implicit conversions, while casts (which have no column) also turn any
+    // value into text and convert between BOOLEAN and integers.
+    fn convert_cell(&self, cell: Cell, data_type: &DataType, size: u32, nullable: bool, column_name: Option<&String>) -> Result<Cell, Error> {
+        if let DataType::USERDEFINED(name) = data_type {
+            return self.convert_user_type(cell, name, size, nullable, column_name);
+        }
+        let explicit = column_name.is_none();
+        let target = match column_name {
+            Some(name) => format!(" for column '{}'", name),
+            None => String::new()
+        };
+        let mismatch = match column_name {
+            Some(name) => Error::new(TYPE_ERROR, format!("Column '{}' is of type {} but expression is of type {}", name, data_type, cell.type_name())),
+            None => Error::new(TYPE_ERROR, format!("Cannot cast {} to {}", cell.type_name(), data_type))
+        };
+        let is_text_type = matches!(data_type, DataType::STRING | DataType::TEXT | DataType::CHAR);
+        let cell = match cell {
+            Cell::NULL => return Ok(Cell::NULL),
+            // Strings are read in the text form of the target type
+            Cell::STRING(text, _, _) | Cell::CHAR(text, _, _) if !is_text_type => self.parse_text(&text, data_type, &target)?,
+            _ if explicit && is_text_type => Cell::STRING(self.format_text(&cell), 0, false),
+            _ => cell
+        };
+        match data_type {
+            DataType::INT | DataType::BIGINT | DataType::SMALLINT => {
+                let range_error = Error::new(DATA_ERROR, format!("{} out of range{}", data_type, target));
+                let value = match cell {
+                    Cell::DOUBLE(value, _, _) => {
+                        if !(value.round() >= i64::MIN as f64 && value.round() <= i64::MAX as f64) {
+                            return Err(range_error);
+                        }
+                        value.round() as i64
+                    },
+                    Cell::DECIMAL(value, scale, _, _) => {
+                        match decimal::rescale(value, scale, 0).and_then(|value| i64::try_from(value).ok()) {
+                            Some(value) => value,
+                            None => return Err(range_error)
+                        }
+                    },
+                    Cell::BOOLEAN(value, _, _) if explicit => value as i64,
+                    _ => match cell.as_i64() {
+                        Some(value) => value,
+                        None => return Err(mismatch)
+                    }
+                };
+                match data_type {
+                    DataType::INT => match i32::try_from(value) {
+                        Ok(value) => Ok(Cell::INT(value, size, nullable)),
+                        Err(_) => Err(range_error)
+                    },
+                    DataType::SMALLINT => match i16::try_from(value) {
+                        Ok(value) => Ok(Cell::SMALLINT(value, size, nullable)),
+                        Err(_) => Err(range_error)
+                    },
+                    _ => Ok(Cell::BIGINT(value, size, nullable))
+                }
+            },
+            DataType::DOUBLE => match cell.as_f64() {
+                Some(value) => Ok(Cell::DOUBLE(value, size, nullable)),
+                None => Err(mismatch)
+            },
+            DataType::BOOLEAN => match cell {
+                Cell::BOOLEAN(value, _, _) => Ok(Cell::BOOLEAN(value, size, nullable)),
+                Cell::SMALLINT(_, _, _) | Cell::INT(_, _, _) | Cell::BIGINT(_, _, _) if explicit => Ok(Cell::BOOLEAN(cell.as_i64().unwrap() != 0, size, nullable)),
+                _ => Err(mismatch)
+            },
+            DataType::DECIMAL(precision, scale) => {
+                let overflow = Error::new(DATA_ERROR, format!("Numeric field overflow: value does not fit {}{}", data_type, target));
+                let (value, value_scale) = match cell {
+                    // The shortest decimal text of the double is rounded
+                    // below like any other decimal
+                    Cell::DOUBLE(value, _, _) => match decimal::parse_decimal(&value.to_string()) {
+                        Some(value) => value,
+                        None => return Err(overflow)
+                    },
+                    _ => match cell.as_decimal() {
+                        Some(value) => value,
+                        None => return Err(mismatch)
                     }
+                };
+                // Round to the target scale, then check the precision
+                match decimal::rescale(value, value_scale, *scale) {
+                    Some(value) if decimal::precision_of(value) <= *precision => Ok(Cell::DECIMAL(value, *scale, size, nullable)),
+                    _ => Err(overflow)
                 }
             },
-            Cell::STRING(value1, _, _) => {
-                match cell2 {
-                    Cell::INT(_, _, _) => {
-                        return false;
+            DataType::DATE | DataType::TIME | DataType::TIMESTAMP | DataType::TIMESTAMPTZ | DataType::INTERVAL => {
+                match (data_type, cell) {
+                    (DataType::DATE, Cell::DATE(value, _, _)) => Ok(Cell::DATE(value, size, nullable)),
+                    (DataType::DATE, Cell::TIMESTAMP(value, _, _)) => Ok(Cell::DATE(value.div_euclid(MICROS_PER_DAY) as i32, size, nullable)),
+                    (DataType::DATE, Cell::TIMESTAMPTZ(value, offset, _, _)) => {
+                        let local = value + offset as i64 * MICROS_PER_SECOND;
+                        Ok(Cell::DATE(local.div_euclid(MICROS_PER_DAY) as i32, size, nullable))
                     },
-                    Cell::STRING(value2, _, _) => {
-                        return value1 == value2;
+                    (DataType::TIME, Cell::TIME(value, _, _)) => Ok(Cell::TIME(value, size, nullable)),
+                    (DataType::TIME, Cell::TIMESTAMP(value, _, _)) => Ok(Cell::TIME(value.rem_euclid(MICROS_PER_DAY), size, nullable)),
+                    (DataType::TIMESTAMP, Cell::TIMESTAMP(value, _, _)) => Ok(Cell::TIMESTAMP(value, size, nullable)),
+                    (DataType::TIMESTAMP, Cell::DATE(value, _, _)) => Ok(Cell::TIMESTAMP(value as i64 * MICROS_PER_DAY, size, nullable)),
+                    // The session time zone is always UTC
+                    (DataType::TIMESTAMP, Cell::TIMESTAMPTZ(value, _, _, _)) => Ok(Cell::TIMESTAMP(value, size, nullable)),
+                    (DataType::TIMESTAMPTZ, Cell::TIMESTAMPTZ(value, offset, _, _)) => Ok(Cell::TIMESTAMPTZ(value, offset, size, nullable)),
+                    (DataType::TIMESTAMPTZ, Cell::TIMESTAMP(value, _, _)) => Ok(Cell::TIMESTAMPTZ(value, 0, size, nullable)),
+                    (DataType::TIMESTAMPTZ, Cell::DATE(value, _, _)) => Ok(Cell::TIMESTAMPTZ(value as i64 * MICROS_PER_DAY, 0, size, nullable)),
+                    (DataType::INTERVAL, Cell::INTERVAL(value, _, _)) => Ok(Cell::INTERVAL(value, size, nullable)),
+                    _ => Err(mismatch)
+                }
+            },
+            DataType::BLOB => match cell {
+                Cell::BLOB(value, _, _) => {
+                    if size > 0 && value.len() as u32 > size {
+                        return Err(Error::new(DATA_ERROR, format!("Value too long for type VARBINARY({}){}", size, target)));
                     }
+                    Ok(Cell::BLOB(value, size, nullable))
+                },
+                _ => Err(mismatch)
+            },
+            DataType::JSON => match cell {
+                Cell::JSON(value, _, _) => Ok(Cell::JSON(value, size, nullable)),
+                _ => Err(mismatch)
+            },
+            DataType::UUID => match cell {
+                Cell::UUID(value, _, _) => Ok(Cell::UUID(value, size, nullable)),
+                _ => Err(mismatch)
+            },
+            // Enums and domains are converted by convert_user_type
+            DataType::USERDEFINED(_) => Err(mismatch),
+            DataType::ARRAY(element_type) => match cell {
+                Cell::ARRAY(elements, _, _) => {
+                    let mut converted = Vec::new();
+                    for element in elements {
+                        converted.push(self.convert_cell(element, element_type, size, true, column_name)?);
+                    }
+                    Ok(Cell::ARRAY(converted, size, nullable))
+                },
+                _ => Err(mismatch)
+            },
+            DataType::STRING | DataType::TEXT | DataType::CHAR => {
+                let value = match cell {
+                    Cell::STRING(value, _, _) => value,
+                    Cell::CHAR(value, _, _) => value.trim_end_matches(' ').to_string(),
+                    _ => return Err(mismatch)
+                };
+                // Casts cut the value to the length without complaint
+                let value = match (data_type, column_name) {
+                    (DataType::TEXT, _) => value,
+                    (_, None) => value.chars().take(size as usize).collect(),
+                    (_, Some(name)) => self.fit_string(value, data_type, size, name)?
+                };
+                match data_type {
+                    DataType::CHAR => {
+                        let padding = size as usize - value.chars().count();
+                        Ok(Cell::CHAR(format!("{}{}", value, " ".repeat(padding)), size, nullable))
+                    },
+                    _ => Ok(Cell::STRING(value, size, nullable))
                 }
             }
         }
     }
 
-    pub fn create_table(&mut self, name: String) {
-        self.tables.insert(name, Box::new(Table { cursor: 0, row_count: 0, columns: Vec::new(), rows: Vec::new()}));
-    }
-
-    pub fn drop_table(&mut self, name: String) {
-        self.tables.remove(&name);
+    // Reads a value of the given type from its text form
+    fn parse_text(&self, text: &String, data_type: &DataType, target: &String) -> Result<Cell, Error> {
+        let trimmed = text.trim();
+        let invalid = Error::new(DATA_ERROR, format!("Invalid input syntax for type {}{}: '{}'", data_type, target, text));
+        match data_type {
+            DataType::INT | DataType::BIGINT | DataType::SMALLINT => match trimmed.parse::<i64>() {
+                Ok(value) => Ok(Cell::BIGINT(value, 0, false)),
+                Err(_) => Err(invalid)
+            },
+            DataType::DOUBLE | DataType::DECIMAL(_, _) => {
+                if let (DataType::DECIMAL(_, _), Some((value, scale))) = (data_type, decimal::parse_decimal(trimmed)) {
+                    return Ok(Cell::DECIMAL(value, scale, 0, false));
+                }
+                match trimmed.parse::<f64>() {
+                    Ok(value) if value.is_finite() => Ok(Cell::DOUBLE(value, 0, false)),
+                    _ => Err(invalid)
+                }
+            },
+            DataType::BOOLEAN => match trimmed.to_lowercase().as_str() {
+                "true" | "t" | "yes" | "y" | "on" | "1" => Ok(Cell::BOOLEAN(true, 0, false)),
+                "false" | "f" | "no" | "n" | "off" | "0" => Ok(Cell::BOOLEAN(false, 0, false)),
+                _ => Err(invalid)
+            },
+            DataType::DATE | DataType::TIME | DataType::TIMESTAMP | DataType::TIMESTAMPTZ | DataType::INTERVAL => self.parse_datetime(text, data_type),
+            // Text written as \x0102 holds hex digits, anything else is
+            // taken as its UTF-8 bytes
+            DataType::BLOB => match text.strip_prefix("\\x") {
+                Some(hex) => self.parse_hex(&hex.to_string()),
+                None => Ok(Cell::BLOB(text.as_bytes().to_vec(), 0, false))
+            },
+            DataType::JSON => match json::parse_json(text) {
+                Ok(value) => Ok(Cell::JSON(value, 0, false)),
+                Err(message) => Err(Error::new(DATA_ERROR, format!("Invalid JSON{}: {}", target, message)))
+            },
+            DataType::UUID => match uuid::parse_uuid(text) {
+                Some(value) => Ok(Cell::UUID(value, 0, false)),
+                None => Err(invalid)
+            },
+            DataType::ARRAY(_) => match self.parse_array_text(text) {
+                Some(elements) => Ok(Cell::ARRAY(elements, 0, false)),
+                None => Err(invalid)
+            },
+            DataType::STRING | DataType::TEXT | DataType::CHAR | DataType::USERDEFINED(_) => Ok(Cell::STRING(text.clone(), 0, false))
+        }
     }
 
-    pub fn truncate_table(&mut self, name: String) {
-        let table = self.tables.get_mut(&name).expect("Could not find table");
-        table.rows.clear();
-        table.row_count = 0;
+    // Reads an array written as {1,2,NULL} or {'a','b c'}. The elements
+    // are left as strings for the element type to convert.
+    fn parse_array_text(&self, text: &String) -> Option<Vec<Cell>> {
+        let inner = text.trim().strip_prefix('{')?.strip_suffix('}')?;
+        let mut elements = Vec::new();
+        if inner.trim().is_empty() {
+            return Some(elements);
+        }
+        let mut characters = inner.chars().peekable();
+        loop {
+            while characters.peek() == Some(&' ') {
+                characters.next();
+            }
+            let mut element = String::new();
+            let mut quoted = false;
+            if let Some(quote) = characters.peek().cloned().filter(|character| *character == '"' || *character == '\'') {
+                characters.next();
+                quoted = true;
+                loop {
+                    match characters.next()? {
+                        character if character == quote => break,
+                        '\\' => element.push(characters.next()?),
+                        character => element.push(character)
+                    }
+                }
+            }
+            while let Some(character) = characters.next() {
+                if character == ',' {
+                    break;
+                }
+                if !quoted {
+                    element.push(character);
+                }
+            }
+            let element = if quoted { element } else { element.trim().to_string() };
+            if !quoted && element.to_uppercase() == "NULL" {
+                elements.push(Cell::NULL);
+            } else {
+                elements.push(Cell::STRING(element, 0, false));
+            }
+            if characters.peek().is_none() {
+                return Some(elements);
+            }
+        }
     }
 
-    pub fn get_table(&mut self, name: String) -> Option<&Box<Table>> {
-        if let Some(table) = self.tables.get_mut(&name) {
-            return Some(table);
+    // Text form of a value, as produced by a cast to VARCHAR
+    fn format_text(&self, cell: &Cell) -> String {
+        match cell {
+            Cell::STRING(value, _, _) | Cell::CHAR(value, _, _) => value.clone(),
+            Cell::ENUM(ordinal, type_id, _, _) => self.enum_label(*ordinal, *type_id).clone(),
+            Cell::BLOB(value, _, _) => {
+                let hex: Vec<String> = value.iter().map(|byte| format!("{:02x}", byte)).collect();
+                format!("\\x{}", hex.concat())
+            },
+            _ => cell.render()
         }
-        return None;
     }
 
-    pub fn add_table_column(&mut self, name: String, column: Box<Column>) {
-        let table = self.tables.get_mut(&name).expect("Could not find table");
-        table.columns.push(column.clone());
-        for (_, table) in &mut self.tables {
-            for row in &mut table.rows {
-                match column.data_type {
-                    DataType::INT => {
-                        row.push(Cell::INT(0, column.size, column.nullable));
-                    },
-                    DataType::STRING => {
-                        row.push(Cell::STRING("".to_string(), column.size, column.nullable));
-                    },
-                    _ => panic!("Unsupported data type")
-                }
-            }
+    // Checks a string against the column length, counted in characters.
+    // Trailing spaces beyond the length are dropped silently, as in the SQL
+    // standard.
+    fn fit_string(&self, value: String, data_type: &DataType, size: u32, column_name: &String) -> Result<String, Error> {
+        let length = value.chars().count();
+        if length <= size as usize {
+            return Ok(value);
+        }
+        let truncated: String = value.chars().take(size as usize).collect();
+        if value[truncated.len()..].chars().all(|character| character == ' ') {
+            return Ok(truncated);
+        }
+        if self.strict_mode {
+            return Err(Error::new(DATA_ERROR, format!("Value too long for type {}({}) in column '{}'", data_type, size, column_name)));
         }
+        println!("Warning: Value truncated from {} to {} characters in column '{}'", length, size, column_name);
+        return Ok(truncated);
     }
 
-    pub fn describe_tables(&mut self) {
-        let mut table_names: Vec<&String> = self.tables.keys().collect();
-        table_names.sort();
-        for name in table_names {
-            let table = self.tables.get(name).unwrap();
-            println!("Table name: {}", name);
-            println!("\tRow count: {}", table.row_count);
-            println!("\tColumn count: {}", table.columns.len());
+    pub fn set_variable(&mut self, name: String, value: String) -> Result<(), Error> {
+        match name.as_str() {
+            "STRICT_MODE" => {
+                self.strict_mode = match value.as_str() {
+                    "ON" | "TRUE" | "1" => true,
+                    "OFF" | "FALSE" | "0" => false,
+                    _ => return Err(Error::new(DATA_ERROR, format!("Invalid value for {}: {}", name, value)))
+                };
+                return Ok(());
+            },
+            _ => Err(Error::new(CATALOG_ERROR, format!("Unrecognized configuration parameter: {}", name)))
         }
     }
 
-    pub fn insert_into_table(&mut self, name: String, columns: Vec<String>, values: Vec<String>) {
-        let table = self.tables.get_mut(&name).expect("Could not find table");
+    pub fn insert_into_table(&mut self, name: String, columns: Vec<String>, values: Vec<Expression>) -> Result<(), Error> {
+        let name = self.resolve_name(&name);
+        self.check_writable(&name)?;
+        let table = match self.tables.get(&name) {
+            Some(table) => table,
+            None => return Err(Error::new(CATALOG_ERROR, format!("Table not found: {}", name)))
+        };
         let table_columns = &table.columns;
 
         // Check if all columns exist
-        let mut columns_missing = 0;
         for column in &columns {
             let c = table.find_column(column.clone());
             if c.is_none() {
-                columns_missing += 1;
-                panic!("Column not found: {}", column);
+                return Err(Error::new(CATALOG_ERROR, format!("Column not found: {}", column)));
             }
         }
-        if columns_missing > 0 {
-            panic!("One or more columns do not exist in the table");
+        if columns.len() != values.len() {
+            return Err(Error::new(CATALOG_ERROR, format!("Expected {} values but got {}", columns.len(), values.len())));
         }
 
         // Insert row
         let mut row: Vec<Cell> = Vec::new();
         for column in table_columns {
-            for i in 0..columns.len() {
-                if columns[i] == column.name {
-                    
-                    let mut value = values[i].clone();
-                    match column.data_type {
-                        DataType::INT => {
-                            let value = value.parse::<i32>().expect("Could not parse integer");
-                            row.push(Cell::INT(value, column.size, column.nullable));
-                        },
-                        DataType::STRING => {
-                            if (value.len() as u32) > column.size {
-                                value = value.chars().take(column.size as usize).collect();
-                            }
-                            row.push(Cell::STRING(value, column.size, column.nullable));
-                        },
-                        _ => panic!("Unsupported data type")
-                    }
+            let value = match columns.iter().position(|name| *name == column.name) {
+                Some(index) if values[index] != Expression::Default && column.identity == Some(Identity::Always) => {
+                    return Err(Error::new(CONSTRAINT_ERROR, format!("Cannot insert a non-DEFAULT value into column '{}', which is GENERATED ALWAYS AS IDENTITY", column.name)));
+                },
+                Some(index) if values[index] != Expression::Default => self.evaluate_expression(&values[index], &Vec::new(), &Vec::new())?,
+                _ => match &column.default {
+                    Some(default) => self.evaluate_expression(default, &Vec::new(), &Vec::new())?,
+                    None => Cell::NULL
+                }
+            };
+            if let Cell::NULL = value {
+                if !column.nullable {
+                    return Err(Error::new(CONSTRAINT_ERROR, format!("Null value in column '{}' violates NOT NULL constraint", column.name)));
+                }
+            }
+            row.push(self.coerce_to_column(value, column)?);
+        }
+        for check in &table.checks {
+            self.check_row(&name, table, check, &row)?;
+        }
+        let mut keys = Vec::new();
+        for unique_index in &table.indexes {
+            let key = table.index_key(&unique_index.columns, &row);
+            if let Some(key) = &key {
+                if unique_index.keys.contains(key) {
+                    return Err(unique_index.duplicate_key_error(key));
                 }
             }
+            keys.push(key);
+        }
+        for foreign_key in &table.foreign_keys {
+            self.check_foreign_key(&name, table, foreign_key, &row)?;
+        }
+        let table = self.tables.get_mut(&name).unwrap();
+        for (unique_index, key) in table.indexes.iter_mut().zip(keys) {
+            if let Some(key) = key {
+                unique_index.keys.insert(key);
+            }
         }
         table.insert_row(row);
-        self.tables.get_mut(&name).unwrap().row_count += 1;
+        table.row_count += 1;
+        return Ok(());
     }
 
-    pub fn select_from_table(&mut self, name: String, distinct: bool, columns: Vec<String>) {
-        let table = self.tables.get(&name).expect("Could not find table");
-        let table_columns = &table.columns;
+    // Builds the rows of a table function used in FROM. unnest(array)
+    // produces one row per element.
+    fn evaluate_table_function(&self, expression: &Expression, column_name: &String) -> Result<Table, Error> {
+        let (name, arguments) = match expression {
+            Expression::Function { name, arguments } => (name, arguments),
+            _ => return Err(Error::new(SYNTAX_ERROR, "Expected a table function in FROM".to_string()))
+        };
+        if name != "UNNEST" {
+            return Err(Error::new(CATALOG_ERROR, format!("Table function not found: {}", name)));
+        }
+        let mut values = Vec::new();
+        for argument in arguments {
+            values.push(self.evaluate_expression(argument, &Vec::new(), &Vec::new())?);
+        }
+        self.expect_arguments(name, &values, 1)?;
+        let elements = match values.remove(0) {
+            Cell::NULL => Vec::new(),
+            Cell::ARRAY(elements, _, _) => elements,
+            value => return Err(Error::new(TYPE_ERROR, format!("Function UNNEST is not defined for {}", value.type_name())))
+        };
+        let data_type = match elements.iter().find(|element| !matches!(element, Cell::NULL)) {
+            Some(element) => self.data_type_of(element),
+            None => DataType::TEXT
+        };
+        let column = Column { name: column_name.clone(), data_type: data_type, size: 0, nullable: true, default: None, identity: None };
+        let rows: Vec<Vec<Cell>> = elements.into_iter().map(|element| vec![element]).collect();
+        return Ok(Table { cursor: 0, row_count: rows.len() as i32, columns: vec![Box::new(column)], rows: rows, missing: vec![Cell::NULL], indexes: Vec::new(), foreign_keys: Vec::new(), checks: Vec::new() });
+    }
 
-        // Check if all columns exist
-        let mut columns_missing = 0;
-        for column in &columns {
-            if column != "*" {
-                let c = table.find_column(column.clone());
-                if c.is_none() {
-                    columns_missing += 1;
-                    panic!("Column not found: {}", column);
+    // The information_schema views, built from the catalog each time they
+    // are read. Tables are listed by schema and name, their columns in order.
+    fn information_schema_view(&self, name: &String) -> Option<Table> {
+        let text = |value: &str| Cell::STRING(value.to_string(), 0, false);
+        let integer = |value: Option<u32>| value.map_or(Cell::NULL, |value| Cell::INT(value as i32, 0, false));
+        let yes_no = |value: bool| text(if value { "YES" } else { "NO" });
+        let mut table_names: Vec<&String> = self.tables.keys().collect();
+        table_names.sort_by_key(|table_name| Database::split_name(table_name));
+        let mut rows = Vec::new();
+        let columns = match name.as_str() {
+            "information_schema.schemata" => {
+                let mut schemas = self.schemas.clone();
+                schemas.sort();
+                for schema in schemas {
+                    rows.push(vec![text(&schema)]);
                 }
-            }
+                vec![("schema_name", DataType::TEXT)]
+            },
+            "information_schema.tables" => {
+                for table_name in &table_names {
+                    let (schema, table_name) = Database::split_name(table_name);
+                    let table_type = if schema == TEMPORARY_SCHEMA { "LOCAL TEMPORARY" } else { "BASE TABLE" };
+                    rows.push(vec![text(schema), text(table_name), text(table_type)]);
+                }
+                vec![("table_schema", DataType::TEXT), ("table_name", DataType::TEXT), ("table_type", DataType::TEXT)]
+            },
+            "information_schema.columns" => {
+                for key in &table_names {
+                    let (schema, table_name) = Database::split_name(key);
+                    for (i, column) in self.tables[*key].columns.iter().enumerate() {
+                        let (data_type, length, precision, scale) = match &column.data_type {
+                            DataType::DECIMAL(precision, scale) => ("DECIMAL".to_string(), None, Some(*precision), Some(*scale)),
+                            DataType::STRING | DataType::CHAR => (column.data_type.to_string(), Some(column.size), None, None),
+                            DataType::BLOB if column.size > 0 => ("VARBINARY".to_string(), Some(column.size), None, None),
+                            data_type => (data_type.to_string(), None, None, None)
+                        };
+                        let default = column.default.as_ref().map_or(Cell::NULL, |default| text(&default.to_string()));
+                        let generation = match column.identity {
+                            Some(Identity::Always) => text("ALWAYS"),
+                            Some(Identity::ByDefault) => text("BY DEFAULT"),
+                            None => Cell::NULL
+                        };
+                        rows.push(vec![text(schema), text(table_name), text(&column.name), integer(Some(i as u32 + 1)), default, yes_no(column.nullable),
+                            text(&data_type), integer(length), integer(precision), integer(scale), yes_no(column.identity.is_some()), generation]);
+                    }
+                }
+                vec![("table_schema", DataType::TEXT), ("table_name", DataType::TEXT), ("column_name", DataType::TEXT), ("ordinal_position", DataType::INT),
+                    ("column_default", DataType::TEXT), ("is_nullable", DataType::TEXT), ("data_type", DataType::TEXT), ("character_maximum_length", DataType::INT),
+                    ("numeric_precision", DataType::INT), ("numeric_scale", DataType::INT), ("is_identity", DataType::TEXT), ("identity_generation", DataType::TEXT)]
+            },
+            "information_schema.table_constraints" | "information_schema.key_column_usage" => {
+                for key in &table_names {
+                    let table = &self.tables[*key];
+                    let (schema, table_name) = Database::split_name(key);
+                    let mut constraints: Vec<(&String, &str, &Vec<String>)> = Vec::new();
+                    for index in &table.indexes {
+                        constraints.push((&index.name, if index.primary { "PRIMARY KEY" } else { "UNIQUE" }, &index.columns));
+                    }
+                    for foreign_key in &table.foreign_keys {
+                        constraints.push((&foreign_key.name, "FOREIGN KEY", &foreign_key.columns));
+                    }
+                    for (constraint_name, constraint_type, constraint_columns) in constraints {
+                        if name == "information_schema.table_constraints" {
+                            rows.push(vec![text(constraint_name), text(schema), text(table_name), text(constraint_type)]);
+                            continue;
+                        }
+                        for (i, column_name) in constraint_columns.iter().enumerate() {
+                            rows.push(vec![text(constraint_name), text(schema), text(table_name), text(column_name), integer(Some(i as u32 + 1))]);
+                        }
+                    }
+                    // Checks are not on a list of columns, so they are only
+                    // listed as constraints
+                    if name == "information_schema.table_constraints" {
+                        for check in &table.checks {
+                            rows.push(vec![text(&check.name), text(schema), text(table_name), text("CHECK")]);
+                        }
+                    }
+                }
+                if name == "information_schema.table_constraints" {
+                    vec![("constraint_name", DataType::TEXT), ("table_schema", DataType::TEXT), ("table_name", DataType::TEXT), ("constraint_type", DataType::TEXT)]
+                } else {
+                    vec![("constraint_name", DataType::TEXT), ("table_schema", DataType::TEXT), ("table_name", DataType::TEXT), ("column_name", DataType::TEXT), ("ordinal_position", DataType::INT)]
+                }
+            },
+            _ => return None
+        };
+        let columns: Vec<Box<Column>> = columns.into_iter().map(|(column_name, data_type)| {
+            Box::new(Column { name: column_name.to_string(), data_type: data_type, size: 0, nullable: true, default: None, identity: None })
+        }).collect();
+        let missing = vec![Cell::NULL; columns.len()];
+        return Some(Table { cursor: 0, row_count: rows.len() as i32, columns: columns, rows: rows, missing: missing, indexes: Vec::new(), foreign_keys: Vec::new(), checks: Vec::new() });
+    }
+
+    // The information_schema views cannot be written to
+    fn check_writable(&self, name: &String) -> Result<(), Error> {
+        if name.starts_with("information_schema.") {
+            return Err(Error::new(CATALOG_ERROR, format!("Table '{}' is a read-only system view", name)));
         }
-        if columns_missing > 0 {
-            panic!("One or more columns do not exist in the table");
+        return Ok(());
+    }
+
+    // The type of a computed value. Values without a type of their own,
+    // such as NULL, are taken as TEXT.
+    fn data_type_of(&self, cell: &Cell) -> DataType {
+        match cell {
+            Cell::INT(_, _, _) => DataType::INT,
+            Cell::STRING(_, _, _) => DataType::TEXT,
+            Cell::CHAR(_, _, _) => DataType::CHAR,
+            Cell::BIGINT(_, _, _) => DataType::BIGINT,
+            Cell::SMALLINT(_, _, _) => DataType::SMALLINT,
+            Cell::DOUBLE(_, _, _) => DataType::DOUBLE,
+            Cell::BOOLEAN(_, _, _) => DataType::BOOLEAN,
+            Cell::DECIMAL(value, scale, _, _) => DataType::DECIMAL(decimal::precision_of(*value).max(*scale + 1).min(decimal::MAX_PRECISION), *scale),
+            Cell::DATE(_, _, _) => DataType::DATE,
+            Cell::TIME(_, _, _) => DataType::TIME,
+            Cell::TIMESTAMP(_, _, _) => DataType::TIMESTAMP,
+            Cell::TIMESTAMPTZ(_, _, _, _) => DataType::TIMESTAMPTZ,
+            Cell::INTERVAL(_, _, _) => DataType::INTERVAL,
+            Cell::BLOB(_, _, _) => DataType::BLOB,
+            Cell::JSON(_, _, _) => DataType::JSON,
+            Cell::UUID(_, _, _) => DataType::UUID,
+            Cell::ENUM(_, type_id, _, _) => DataType::USERDEFINED(self.user_types[*type_id as usize].name().clone()),
+            Cell::ARRAY(elements, _, _) => match elements.iter().find(|element| !matches!(element, Cell::NULL)) {
+                Some(element) => DataType::ARRAY(Box::new(self.data_type_of(element))),
+                None => DataType::ARRAY(Box::new(DataType::TEXT))
+            },
+            Cell::NULL => DataType::TEXT
         }
+    }
 
-        println!("Results:");
+    // Runs a query. Result columns take the type of their expression, so a
+    // query that returns no rows still has typed columns.
+    fn run_query(&self, query: &SelectQuery) -> Result<ResultSet, Error> {
+        // A select without FROM is evaluated once against an empty row
+        let no_table = Table { cursor: 0, row_count: 1, columns: Vec::new(), rows: vec![Vec::new()], missing: Vec::new(), indexes: Vec::new(), foreign_keys: Vec::new(), checks: Vec::new() };
+        let function_table;
+        let view_table;
+        let table: &Table = match &query.from {
+            Some(FromItem::Table(name)) => match self.tables.get(&self.resolve_name(name)) {
+                Some(table) => table,
+                None => match self.information_schema_view(&self.resolve_name(name)) {
+                    Some(table) => {
+                        view_table = table;
+                        &view_table
+                    },
+                    None => return Err(Error::new(CATALOG_ERROR, format!("Table not found: {}", name)))
+                }
+            },
+            Some(FromItem::Function { expression, column_name, .. }) => {
+                function_table = self.evaluate_table_function(expression, column_name)?;
+                &function_table
+            },
+            None => &no_table
+        };
 
         let mut outputColumns = Vec::new();
         let mut outputRows = Vec::new();
 
-        // Get column indexes
-        let mut column_indexes: Vec<usize> = Vec::new();
-        for column in &columns {
-            if column == "*" {
-                for (index, table_column) in table_columns.iter().enumerate() {
-                    column_indexes.push(index);
-                    //print!(" {:<15} |", table_column.name);
+        // Expand * into the table columns
+        let mut expressions: Vec<Expression> = Vec::new();
+        for column in &query.columns {
+            if column.expression == Expression::Column("*".to_string()) {
+                for table_column in &table.columns {
                     outputColumns.push(table_column.name.clone());
+                    expressions.push(Expression::Column(table_column.name.clone()));
                 }
             }
             else {
-                for (index, table_column) in table_columns.iter().enumerate() {
-                    if *column == table_column.name {
-                        column_indexes.push(index);
-                        //print!(" {:<15} |", table_column.name);
-                        outputColumns.push(table_column.name.clone());
-                    }
-                }
+                outputColumns.push(column.name.clone());
+                expressions.push(column.expression.clone());
             }
         }
-        println!();
 
-        for row in &table.rows {
+        if expressions.iter().any(|expression| self.contains_aggregate(expression)) {
             let mut outputRow: Vec<Cell> = Vec::new();
-            for index in &column_indexes {
-                for i in 0..row.len() {
-                    if i == *index {
-                        match &row[i] {
-                            Cell::INT(value, _, _) => {
-                                //print!(" {:<15} |", value);
-                                outputRow.push(Cell::INT(*value, 0, false));
-                            },
-                            Cell::STRING(value, _, _) => {
-                                //print!(" {:<15} |", value);
-                                outputRow.push(Cell::STRING(value.clone(), 0, false));
-                            }
-                        }
-                    }
-                }
+            for expression in &expressions {
+                outputRow.push(self.evaluate_aggregate(expression, table)?);
             }
-            //println!();
             outputRows.push(outputRow);
         }
-
-        for column in outputColumns {
-            print!(" {:<15} |", column);
+        else {
+            for row in &table.rows {
+                let row = table.read_row(row);
+                let mut outputRow: Vec<Cell> = Vec::new();
+                for expression in &expressions {
+                    outputRow.push(self.evaluate_expression(expression, &table.columns, &row)?);
+                }
+                outputRows.push(outputRow);
+            }
         }
-        println!();
 
-        if distinct {
+        if query.distinct {
             let mut distinctRows: Vec<Vec<Cell>> = Vec::new();
             for outputRow in outputRows {
                 let mut unique = true;
@@ -266,28 +3017,271 @@ impl Database {
             outputRows = distinctRows;
         }
 
-        for row in outputRows {
-            for cell in row {
-                match cell {
-                    Cell::INT(value, _, _) => {
-                        print!(" {:<15} |", value);
+        let mut columns = Vec::new();
+        for (name, expression) in outputColumns.into_iter().zip(&expressions) {
+            let (data_type, size) = self.expression_type(expression, &table.columns);
+            columns.push(Column { name: name, data_type: data_type, size: size, nullable: true, default: None, identity: None });
+        }
+        return Ok(ResultSet { columns: columns, rows: outputRows });
+    }
+
+    // The type of a result column, worked out from its expression and the
+    // types of the columns it reads rather than from the rows it returns.
+    // Only columns and casts carry a size.
+    fn expression_type(&self, expression: &Expression, columns: &Vec<Box<Column>>) -> (DataType, u32) {
+        match expression {
+            Expression::Column(name) => match columns.iter().find(|column| column.name == *name) {
+                Some(column) => (column.data_type.clone(), column.size),
+                None => (DataType::TEXT, 0)
+            },
+            Expression::Cast { data_type, size, .. } => (data_type.clone(), *size),
+            _ => (self.value_type(expression, columns), 0)
+        }
+    }
+
+    fn value_type(&self, expression: &Expression, columns: &Vec<Box<Column>>) -> DataType {
+        match expression {
+            Expression::Column(_) | Expression::Cast { .. } => self.base_type(&self.expression_type(expression, columns).0),
+            Expression::Number(text) => match self.parse_number(text) {
+                Ok(cell) => self.data_type_of(&cell),
+                Err(_) => DataType::TEXT
+            },
+            Expression::Boolean(_) => DataType::BOOLEAN,
+            Expression::TypedLiteral { data_type, .. } => data_type.clone(),
+            Expression::Unary { operator, operand } => match operator.as_str() {
+                "IS NULL" | "IS NOT NULL" | "NOT" => DataType::BOOLEAN,
+                _ => self.value_type(operand, columns)
+            },
+            Expression::Binary { operator, left, right } => {
+                let left = self.value_type(left, columns);
+                let right = self.value_type(right, columns);
+                match operator.as_str() {
+                    "AND" | "OR" | "=" | "<>" | "!=" | "<" | "<=" | ">" | ">=" => DataType::BOOLEAN,
+                    _ if operator.ends_with(" ANY") || operator.ends_with(" ALL") => DataType::BOOLEAN,
+                    "->" => DataType::JSON,
+                    "->>" => DataType::TEXT,
+                    "[]" => match left {
+                        DataType::ARRAY(element_type) => self.base_type(&element_type),
+                        _ => DataType::TEXT
                     },
-                    Cell::STRING(value, _, _) => {
-                        print!(" {:<15} |", value);
-                    }
+                    _ => self.arithmetic_type(operator, left, right)
+                }
+            },
+            Expression::Function { name, arguments } => {
+                let argument_type = |index: usize| match arguments.get(index) {
+                    Some(argument) => self.value_type(argument, columns),
+                    None => DataType::TEXT
+                };
+                match name.as_str() {
+                    "SUM" => match argument_type(0) {
+                        DataType::SMALLINT | DataType::INT => DataType::BIGINT,
+                        DataType::BIGINT => DataType::DECIMAL(decimal::MAX_PRECISION, 0),
+                        DataType::DECIMAL(_, scale) => DataType::DECIMAL(decimal::MAX_PRECISION, scale),
+                        data_type => data_type
+                    },
+                    "AVG" => match argument_type(0) {
+                        DataType::DOUBLE => DataType::DOUBLE,
+                        DataType::DECIMAL(_, scale) => DataType::DECIMAL(decimal::MAX_PRECISION, scale.max(6)),
+                        _ => DataType::DECIMAL(decimal::MAX_PRECISION, 6)
+                    },
+                    "NOW" | "CURRENT_TIMESTAMP" => DataType::TIMESTAMPTZ,
+                    "CURRENT_DATE" => DataType::DATE,
+                    "LENGTH" | "OCTET_LENGTH" | "JSON_ARRAY_LENGTH" | "JSON_OBJECT_LENGTH" | "ARRAY_LENGTH" | "CARDINALITY" => DataType::INT,
+                    "JSON_EXTRACT" => DataType::JSON,
+                    // Numeric elements are widened the way make_array does
+                    "ARRAY" => {
+                        let is_numeric = |data_type: &DataType| matches!(data_type, DataType::SMALLINT | DataType::INT | DataType::BIGINT | DataType::DECIMAL(_, _) | DataType::DOUBLE);
+                        let mut element_type: Option<DataType> = None;
+                        for argument in arguments.iter().filter(|argument| **argument != Expression::Null) {
+                            let argument_type = self.value_type(argument, columns);
+                            element_type = Some(match element_type {
+                                Some(data_type) if is_numeric(&data_type) && is_numeric(&argument_type) => self.arithmetic_type(&"%".to_string(), data_type, argument_type),
+                                Some(data_type) => data_type,
+                                None => argument_type
+                            });
+                        }
+                        DataType::ARRAY(Box::new(element_type.unwrap_or(DataType::TEXT)))
+                    },
+                    "NEXTVAL" | "CURRVAL" | "SETVAL" => DataType::BIGINT,
+                    "GEN_RANDOM_UUID" => DataType::UUID,
+                    "EXTRACT" => match (arguments.get(0), argument_type(1)) {
+                        (Some(Expression::Text(field)), _) if field == "SECOND" || field == "EPOCH" => DataType::DECIMAL(decimal::MAX_PRECISION, 6),
+                        (Some(Expression::Text(field)), DataType::INTERVAL) if field == "HOUR" => DataType::BIGINT,
+                        _ => DataType::INT
+                    },
+                    "DATE_TRUNC" => match argument_type(1) {
+                        DataType::TIMESTAMPTZ => DataType::TIMESTAMPTZ,
+                        _ => DataType::TIMESTAMP
+                    },
+                    _ => DataType::TEXT
                 }
+            },
+            Expression::Text(_) | Expression::Null | Expression::Default => DataType::TEXT
+        }
+    }
+
+    // The type of an arithmetic result, following evaluate_arithmetic. A
+    // DECIMAL gets enough digits for any result of its operands.
+    fn arithmetic_type(&self, operator: &String, left: DataType, right: DataType) -> DataType {
+        let is_string = |data_type: &DataType| matches!(data_type, DataType::STRING | DataType::CHAR | DataType::TEXT);
+        // Strings combined with numbers are read as numbers
+        let (left, right) = match (is_string(&left), is_string(&right)) {
+            (true, false) => (right.clone(), right),
+            (false, true) => (left.clone(), left),
+            _ => (left, right)
+        };
+        let is_datetime = |data_type: &DataType| matches!(data_type, DataType::DATE | DataType::TIME | DataType::TIMESTAMP | DataType::TIMESTAMPTZ | DataType::INTERVAL);
+        if is_datetime(&left) || is_datetime(&right) {
+            return match (&left, &right) {
+                (DataType::DATE, DataType::DATE) => DataType::INT,
+                (DataType::TIME, DataType::TIME) | (DataType::INTERVAL, DataType::INTERVAL) => DataType::INTERVAL,
+                (DataType::DATE, DataType::TIME) | (DataType::TIME, DataType::DATE) => DataType::TIMESTAMP,
+                (DataType::INTERVAL, other) | (other, DataType::INTERVAL) => match other {
+                    DataType::DATE | DataType::TIMESTAMP => DataType::TIMESTAMP,
+                    DataType::TIME | DataType::TIMESTAMPTZ => other.clone(),
+                    _ => DataType::INTERVAL
+                },
+                (DataType::DATE, _) | (_, DataType::DATE) if !is_datetime(&left) || !is_datetime(&right) => DataType::DATE,
+                _ => DataType::INTERVAL
+            };
+        }
+        // Rank, precision and scale of a numeric type
+        let numeric = |data_type: &DataType| match data_type {
+            DataType::SMALLINT => Some((SMALLINT_RANK, 5, 0)),
+            DataType::INT => Some((INT_RANK, 10, 0)),
+            DataType::BIGINT => Some((BIGINT_RANK, 19, 0)),
+            DataType::DECIMAL(precision, scale) => Some((DECIMAL_RANK, *precision, *scale)),
+            DataType::DOUBLE => Some((DOUBLE_RANK, 0, 0)),
+            _ => None
+        };
+        let ((rank1, precision1, scale1), (rank2, precision2, scale2)) = match (numeric(&left), numeric(&right)) {
+            (Some(left), Some(right)) => (left, right),
+            _ => return DataType::TEXT
+        };
+        match rank1.max(rank2) {
+            SMALLINT_RANK => DataType::SMALLINT,
+            INT_RANK => DataType::INT,
+            BIGINT_RANK => DataType::BIGINT,
+            DOUBLE_RANK => DataType::DOUBLE,
+            _ => {
+                let digits = (precision1 - scale1).max(precision2 - scale2);
+                let (precision, scale) = match operator.as_str() {
+                    "+" | "-" => (digits + 1 + scale1.max(scale2), scale1.max(scale2)),
+                    "*" => (precision1 + precision2, scale1 + scale2),
+                    "/" => (decimal::MAX_PRECISION, scale1.max(scale2).max(6)),
+                    _ => (digits + scale1.max(scale2), scale1.max(scale2))
+                };
+                let precision = precision.min(decimal::MAX_PRECISION);
+                DataType::DECIMAL(precision, scale.min(precision))
+            }
+        }
+    }
+
+    // Values of a domain type are computed as its base type
+    fn base_type(&self, data_type: &DataType) -> DataType {
+        match data_type {
+            DataType::USERDEFINED(name) => match self.find_user_type(name) {
+                Some((_, UserType::Domain { data_type, .. })) => data_type.clone(),
+                _ => data_type.clone()
+            },
+            _ => data_type.clone()
+        }
+    }
+
+    fn print_result_set(&self, result: &ResultSet) {
+        println!("Results:");
+        println!();
+        for column in &result.columns {
+            print!(" {:<15} |", column.name);
+        }
+        println!();
+
+        for row in &result.rows {
+            for cell in row {
+                print!(" {:<15} |", self.render_cell(cell));
             }
             println!();
         }
 
         println!();
     }
+
+    pub fn select_from_table(&mut self, query: SelectQuery, into: Option<String>) -> Result<(), Error> {
+        if into.is_some() {
+            self.check_column_names(&query)?;
+        }
+        let result = self.run_query(&query)?;
+        match into {
+            Some(name) => self.create_table_from_result(name, false, false, result, true),
+            None => {
+                self.print_result_set(&result);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn create_table_as(&mut self, name: String, temporary: bool, if_not_exists: bool, query: SelectQuery, with_data: bool) -> Result<(), Error> {
+        self.check_column_names(&query)?;
+        if if_not_exists && self.tables.contains_key(&self.new_table_name(&name, temporary)?) {
+            println!("Notice: Table '{}' already exists, skipping", name);
+            return Ok(());
+        }
+        let result = self.run_query(&query)?;
+        return self.create_table_from_result(name, temporary, if_not_exists, result, with_data);
+    }
+
+    // A result column computed by an expression is shown under the text of
+    // the expression, which cannot name a table column. Queries that make a
+    // table have to name such columns with AS.
+    fn check_column_names(&self, query: &SelectQuery) -> Result<(), Error> {
+        for item in &query.columns {
+            if !item.aliased && !matches!(item.expression, Expression::Column(_)) {
+                return Err(Error::new(SYNTAX_ERROR, format!("Result column '{}' must be named with AS", item.name)));
+            }
+        }
+        return Ok(());
+    }
+
+    // Creates a table with the columns of a query result, filled with its
+    // rows unless only the schema is wanted
+    fn create_table_from_result(&mut self, name: String, temporary: bool, if_not_exists: bool, result: ResultSet, with_data: bool) -> Result<(), Error> {
+        let key = self.new_table_name(&name, temporary)?;
+        self.check_writable(&key)?;
+        if self.tables.contains_key(&key) && !if_not_exists {
+            return Err(Error::new(CATALOG_ERROR, format!("Table already exists: {}", key)));
+        }
+        for (i, column) in result.columns.iter().enumerate() {
+            if result.columns[..i].iter().any(|other| other.name == column.name) {
+                return Err(Error::new(CATALOG_ERROR, format!("Column '{}' specified more than once", column.name)));
+            }
+        }
+        let mut rows = Vec::new();
+        if with_data {
+            for row in result.rows {
+                let mut converted = Vec::new();
+                for (cell, column) in row.into_iter().zip(&result.columns) {
+                    converted.push(self.coerce_to_column(cell, column)?);
+                }
+                rows.push(converted);
+            }
+        }
+        self.create_table(name, temporary, false, result.columns, Vec::new())?;
+        let table = self.tables.get_mut(&key).unwrap();
+        table.row_count = rows.len() as i32;
+        table.rows = rows;
+        return Ok(());
+    }
 }
 
 fn main() {
     let args: Vec<String> = env::args().collect();
     let mut database = Database {
         tables: HashMap::new(),
+        schemas: vec!["public".to_string(), "information_schema".to_string(), TEMPORARY_SCHEMA.to_string()],
+        current_schema: "public".to_string(),
+        user_types: Vec::new(),
+        sequences: RefCell::new(HashMap::new()),
+        strict_mode: true
     };
     let file_path: &String = &args[1];
     let content = fs::read_to_string(file_path).expect("Cannot read file");
@@ -305,34 +3299,41 @@ fn main() {
             Some(node) => {
                 
                 match *node.unwrap() {
-                    ASTNode::CreateStatement { table_name, columns_to_add, next } => {
+                    ASTNode::CreateStatement { table_name, temporary, if_not_exists, columns_to_add, constraints, next } => {
                         println!("Command: Create statement");
-                        if database.get_table(table_name.clone()).is_some() {
-                            panic!("Table already exists");
-                        }
-                        database.create_table(table_name.clone());
-                        for column in columns_to_add {
-                            database.add_table_column(table_name.clone(), Box::new(column.clone()));
+                        if let Err(error) = database.create_table(table_name, temporary, if_not_exists, columns_to_add, constraints) {
+                            error.print();
                         }
                         stack.pop();
                         if next.is_some() {
                             stack.push(next);
                         }
                     },
-                    ASTNode::DropStatement { table_name, next } => {
+                    ASTNode::DropStatement { table_name, if_exists, cascade, next } => {
                         println!("Command: Drop statement");
-                        if database.get_table(table_name.clone()).is_none() {
-                            panic!("Table does already exists");
+                        if let Err(error) = database.drop_table(table_name, if_exists, cascade) {
+                            error.print();
                         }
-                        database.drop_table(table_name.clone());
                         stack.pop();
                         if next.is_some() {
                             stack.push(next);
                         }
                     },
-                    ASTNode::SelectStatement { table_name, distinct, columns, next } => {
+                    ASTNode::SelectStatement { query, into, next } => {
                         println!("Command: Select statement");
-                        database.select_from_table(table_name.clone(), distinct, columns);
+                        if let Err(error) = database.select_from_table(query, into) {
+                            error.print();
+                        }
+                        stack.pop();
+                        if next.is_some() {
+                            stack.push(next);
+                        }
+                    },
+                    ASTNode::CreateTableAsStatement { table_name, temporary, if_not_exists, query, with_data, next } => {
+                        println!("Command: Create statement");
+                        if let Err(error) = database.create_table_as(table_name, temporary, if_not_exists, query, with_data) {
+                            error.print();
+                        }
                         stack.pop();
                         if next.is_some() {
                             stack.push(next);
@@ -340,7 +3341,9 @@ fn main() {
                     },
                     ASTNode::InsertStatement { table_name, columns, values, next } => {
                         println!("Command: Insert statement");
-                        database.insert_into_table(table_name.clone(), columns, values);
+                        if let Err(error) = database.insert_into_table(table_name.clone(), columns, values) {
+                            error.print();
+                        }
                         stack.pop();
                         if next.is_some() {
                             stack.push(next);
@@ -354,19 +3357,123 @@ fn main() {
                             stack.push(next);
                         }
                     },
-                    ASTNode::AlterTableStatement { table_name, columns_to_add, next } => {
+                    ASTNode::CreateSchemaStatement { schema_name, if_not_exists, next } => {
+                        println!("Command: Create statement");
+                        if let Err(error) = database.create_schema(schema_name, if_not_exists) {
+                            error.print();
+                        }
+                        stack.pop();
+                        if next.is_some() {
+                            stack.push(next);
+                        }
+                    },
+                    ASTNode::DropSchemaStatement { schema_name, if_exists, cascade, next } => {
+                        println!("Command: Drop statement");
+                        if let Err(error) = database.drop_schema(schema_name, if_exists, cascade) {
+                            error.print();
+                        }
+                        stack.pop();
+                        if next.is_some() {
+                            stack.push(next);
+                        }
+                    },
+                    ASTNode::UseStatement { schema_name, next } => {
+                        println!("Command: Use statement");
+                        if let Err(error) = database.use_schema(schema_name) {
+                            error.print();
+                        }
+                        stack.pop();
+                        if next.is_some() {
+                            stack.push(next);
+                        }
+                    },
+                    ASTNode::ShowCreateTableStatement { table_name, next } => {
+                        println!("Command: Show create table statement");
+                        if let Err(error) = database.show_create_table(&table_name) {
+                            error.print();
+                        }
+                        stack.pop();
+                        if next.is_some() {
+                            stack.push(next);
+                        }
+                    },
+                    ASTNode::DescribeStatement { table_name, next } => {
+                        println!("Command: Describe statement");
+                        if let Err(error) = database.describe_table(&table_name) {
+                            error.print();
+                        }
+                        stack.pop();
+                        if next.is_some() {
+                            stack.push(next);
+                        }
+                    },
+                    ASTNode::AlterTableStatement { table_name, action, next } => {
                         println!("Command: Alter statement");
-                        for column in columns_to_add {
-                            database.add_table_column(table_name.clone(), Box::new(column.clone()));
+                        if let Err(error) = database.alter_table(table_name, action) {
+                            error.print();
                         }
                         stack.pop();
                         if next.is_some() {
                             stack.push(next);
                         }
                     },
-                    ASTNode::TruncateTableStatement { table_name, next } => {
+                    ASTNode::TruncateTableStatement { table_name, cascade, next } => {
                         println!("Command: Truncate statement");
-                        database.truncate_table(table_name.clone());
+                        if let Err(error) = database.truncate_table(table_name.clone(), cascade) {
+                            error.print();
+                        }
+                        stack.pop();
+                        if next.is_some() {
+                            stack.push(next);
+                        }
+                    },
+                    ASTNode::CreateTypeStatement { type_name, labels, next } => {
+                        println!("Command: Create type statement");
+                        if let Err(error) = database.create_type(type_name, labels) {
+                            error.print();
+                        }
+                        stack.pop();
+                        if next.is_some() {
+                            stack.push(next);
+                        }
+                    },
+                    ASTNode::CreateSequenceStatement { sequence_name, if_not_exists, start, increment, min, max, next } => {
+                        println!("Command: Create sequence statement");
+                        if if_not_exists && database.sequences.borrow().contains_key(&sequence_name) {
+                            println!("Notice: Sequence '{}' already exists, skipping", sequence_name);
+                        } else if let Err(error) = database.create_sequence(sequence_name, start, increment, min, max) {
+                            error.print();
+                        }
+                        stack.pop();
+                        if next.is_some() {
+                            stack.push(next);
+                        }
+                    },
+                    ASTNode::DropSequenceStatement { sequence_name, if_exists, next } => {
+                        println!("Command: Drop sequence statement");
+                        if let Err(error) = database.drop_sequence(sequence_name, if_exists) {
+                            error.print();
+                        }
+                        stack.pop();
+                        if next.is_some() {
+                            stack.push(next);
+                        }
+                    },
+                    ASTNode::CreateDomainStatement { domain_name, data_type, size, nullable, checks, next } => {
+                        println!("Command: Create domain statement");
+                        if let Err(error) = database.create_domain(domain_name, data_type, size, nullable, checks) {
+                            error.print();
+                        }
+                        stack.pop();
+                        if next.is_some() {
+                            stack.push(next);
+                        }
+                    },
+                    ASTNode::SetStatement { name, value, next } => {
+                        println!("Command: Set statement");
+                        if let Err(error) = database.set_variable(name, value) {
+                            error.print();
+                        }
                         stack.pop();
                         if next.is_some() {
                             stack.push(next);
@@ -379,4 +3486,5 @@ fn main() {
             }
         }
     }
+    database.drop_temporary_tables();
 }
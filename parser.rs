@@ -1,3 +1,5 @@
+use std::fmt;
+
 pub struct Parser {
     pub tokens: Vec<String>,
     pub cursor: usize
@@ -6,7 +8,60 @@ pub struct Parser {
 #[derive(PartialEq,Debug,Clone)]
 pub enum DataType {
     INT,
-    STRING
+    STRING,
+    BIGINT,
+    SMALLINT,
+    DOUBLE,
+    BOOLEAN,
+    DECIMAL(u32, u32),
+    DATE,
+    TIME,
+    TIMESTAMP,
+    TIMESTAMPTZ,
+    INTERVAL,
+    BLOB,
+    JSON,
+    TEXT,
+    CHAR,
+    // An enum or domain created with CREATE TYPE or CREATE DOMAIN
+    USERDEFINED(String),
+    ARRAY(Box<DataType>),
+    UUID
+}
+
+impl fmt::Display for DataType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let DataType::DECIMAL(precision, scale) = self {
+            return write!(f, "DECIMAL({},{})", precision, scale);
+        }
+        if let DataType::USERDEFINED(name) = self {
+            return write!(f, "{}", name);
+        }
+        if let DataType::ARRAY(element_type) = self {
+            return write!(f, "{}[]", element_type);
+        }
+        let name = match self {
+            DataType::INT => "INT",
+            DataType::STRING => "VARCHAR",
+            DataType::BIGINT => "BIGINT",
+            DataType::SMALLINT => "SMALLINT",
+            DataType::DOUBLE => "DOUBLE",
+            DataType::BOOLEAN => "BOOLEAN",
+            DataType::DECIMAL(_, _) => "DECIMAL",
+            DataType::DATE => "DATE",
+            DataType::TIME => "TIME",
+            DataType::TIMESTAMP => "TIMESTAMP",
+            DataType::TIMESTAMPTZ => "TIMESTAMPTZ",
+            DataType::INTERVAL => "INTERVAL",
+            DataType::BLOB => "BLOB",
+            DataType::JSON => "JSON",
+            DataType::TEXT => "TEXT",
+            DataType::CHAR => "CHAR",
+            DataType::UUID => "UUID",
+            DataType::USERDEFINED(_) | DataType::ARRAY(_) => ""
+        };
+        return write!(f, "{}", name);
+    }
 }
 
 #[derive(PartialEq,Debug,Clone)]
@@ -14,18 +69,228 @@ pub struct Column {
     pub name: String,
     pub data_type: DataType,
     pub size: u32,
-    pub nullable: bool
+    pub nullable: bool,
+    // Used for the column when an INSERT leaves it out
+    pub default: Option<Expression>,
+    // Set for AUTO_INCREMENT and GENERATED ... AS IDENTITY columns
+    pub identity: Option<Identity>
+}
+
+// A type as written in DDL, with the length of VARCHAR, CHAR and VARBINARY
+pub fn format_data_type(data_type: &DataType, size: u32) -> String {
+    match data_type {
+        DataType::STRING => format!("VARCHAR({})", size),
+        DataType::CHAR => format!("CHAR({})", size),
+        DataType::BLOB if size > 0 => format!("VARBINARY({})", size),
+        DataType::ARRAY(element_type) => format!("{}[]", format_data_type(element_type, size)),
+        _ => data_type.to_string()
+    }
+}
+
+// Written as a column definition of CREATE TABLE. Identity columns keep
+// their sequence in the default and are never NULL, so neither is written.
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.name, format_data_type(&self.data_type, self.size))?;
+        match self.identity {
+            Some(Identity::Always) => return write!(f, " GENERATED ALWAYS AS IDENTITY"),
+            Some(Identity::ByDefault) => return write!(f, " GENERATED BY DEFAULT AS IDENTITY"),
+            None => {}
+        }
+        if let Some(default) = &self.default {
+            write!(f, " DEFAULT {}", default.operand())?;
+        }
+        if !self.nullable {
+            write!(f, " NOT NULL")?;
+        }
+        return Ok(());
+    }
+}
+
+// Whether an identity column also accepts values given by an INSERT
+#[derive(PartialEq,Debug,Clone)]
+pub enum Identity {
+    Always,
+    ByDefault
+}
+
+#[derive(PartialEq,Debug,Clone)]
+pub enum Expression {
+    Column(String),
+    Number(String),
+    Text(String),
+    Boolean(bool),
+    Null,
+    Unary { operator: String, operand: Box<Expression> },
+    Binary { operator: String, left: Box<Expression>, right: Box<Expression> },
+    Function { name: String, arguments: Vec<Expression> },
+    TypedLiteral { data_type: DataType, text: String },
+    Cast { expression: Box<Expression>, data_type: DataType, size: u32 },
+    // The DEFAULT keyword in a VALUES list
+    Default
+}
+
+impl Expression {
+    // The expression as an operand of another, parenthesized unless it binds
+    // tighter than any operator
+    pub fn operand(&self) -> String {
+        match self {
+            Expression::Unary { .. } => format!("({})", self),
+            Expression::Binary { operator, .. } if operator != "[]" => format!("({})", self),
+            Expression::Number(text) if text.starts_with('-') => format!("({})", self),
+            _ => self.to_string()
+        }
+    }
+}
+
+fn quote_text(text: &str) -> String {
+    return format!("'{}'", text.replace('\'', "''"));
+}
+
+// Written back as SQL that parses to the same expression
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expression::Column(name) => write!(f, "{}", name),
+            Expression::Number(text) => write!(f, "{}", text),
+            Expression::Text(text) => write!(f, "{}", quote_text(text)),
+            Expression::Boolean(value) => write!(f, "{}", if *value { "TRUE" } else { "FALSE" }),
+            Expression::Null => write!(f, "NULL"),
+            Expression::Default => write!(f, "DEFAULT"),
+            Expression::Unary { operator, operand } => match operator.as_str() {
+                "-" => write!(f, "-{}", operand.operand()),
+                "NOT" => write!(f, "NOT {}", operand.operand()),
+                _ => write!(f, "{} {}", operand.operand(), operator)
+            },
+            Expression::Binary { operator, left, right } => match operator.as_str() {
+                "[]" => write!(f, "{}[{}]", left.operand(), right),
+                _ if operator.ends_with(" ANY") || operator.ends_with(" ALL") => write!(f, "{} {} ({})", left.operand(), operator, right),
+                _ => write!(f, "{} {} {}", left.operand(), operator, right.operand())
+            },
+            Expression::Function { name, arguments } => {
+                let arguments: Vec<String> = arguments.iter().map(|argument| argument.to_string()).collect();
+                match name.as_str() {
+                    "CURRENT_DATE" | "CURRENT_TIMESTAMP" if arguments.is_empty() => write!(f, "{}", name),
+                    "ARRAY" => write!(f, "ARRAY[{}]", arguments.join(", ")),
+                    "EXTRACT" if arguments.len() == 2 => {
+                        let field = arguments[0].trim_matches('\'');
+                        write!(f, "EXTRACT({} FROM {})", field, arguments[1])
+                    },
+                    _ => write!(f, "{}({})", name, arguments.join(", "))
+                }
+            },
+            Expression::TypedLiteral { data_type: DataType::BLOB, text } => write!(f, "X'{}'", text),
+            Expression::TypedLiteral { data_type, text } => write!(f, "{} {}", data_type, quote_text(text)),
+            Expression::Cast { expression, data_type, size } => write!(f, "CAST({} AS {})", expression, format_data_type(data_type, *size))
+        }
+    }
+}
+
+// A constraint declared on a column or on the table
+#[derive(PartialEq,Debug,Clone)]
+pub struct TableConstraint {
+    // Generated from the table and column names when not given
+    pub name: Option<String>,
+    pub kind: ConstraintKind
+}
+
+#[derive(PartialEq,Debug,Clone)]
+pub enum ConstraintKind {
+    PrimaryKey(Vec<String>),
+    Unique(Vec<String>),
+    // References the primary key of the table when no columns are given
+    ForeignKey { columns: Vec<String>, table: String, references: Vec<String>, on_delete: ReferentialAction, on_update: ReferentialAction },
+    // The column is set when the check is declared on a column, and is
+    // only used to name the constraint
+    Check { expression: Expression, column: Option<String> }
+}
+
+// What happens to referencing rows when the referenced row is deleted or
+// its key is updated
+#[derive(PartialEq,Debug,Clone)]
+pub enum ReferentialAction {
+    NoAction,
+    Restrict,
+    Cascade,
+    SetNull,
+    SetDefault
+}
+
+impl fmt::Display for ReferentialAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ReferentialAction::NoAction => "NO ACTION",
+            ReferentialAction::Restrict => "RESTRICT",
+            ReferentialAction::Cascade => "CASCADE",
+            ReferentialAction::SetNull => "SET NULL",
+            ReferentialAction::SetDefault => "SET DEFAULT"
+        };
+        return write!(f, "{}", name);
+    }
+}
+
+// The change made by an ALTER TABLE statement
+#[derive(PartialEq,Debug,Clone)]
+pub enum AlterAction {
+    // New columns along with the constraints declared on them
+    AddColumns(Vec<Column>, Vec<TableConstraint>),
+    AddConstraint(TableConstraint),
+    RenameColumn { from: String, to: String },
+    RenameTable(String),
+    DropColumn(String),
+    AlterColumnType { column: String, data_type: DataType, size: u32, using: Option<Expression> },
+    SetNotNull(String),
+    DropNotNull(String),
+    SetDefault { column: String, default: Expression },
+    DropDefault(String)
+}
+
+// What a SELECT reads from: a table, or a table function such as
+// unnest(ARRAY[1, 2]) producing a single column
+#[derive(PartialEq,Debug,Clone)]
+pub enum FromItem {
+    Table(String),
+    Function { expression: Expression, alias: String, column_name: String }
+}
+
+#[derive(PartialEq,Debug,Clone)]
+pub struct SelectItem {
+    pub name: String,
+    pub expression: Expression,
+    // Whether the name was given with AS rather than taken from the text
+    pub aliased: bool
+}
+
+// The parts of a SELECT, which also feeds CREATE TABLE ... AS
+#[derive(PartialEq,Debug,Clone)]
+pub struct SelectQuery {
+    pub from: Option<FromItem>,
+    pub distinct: bool,
+    pub columns: Vec<SelectItem>
 }
 
 #[derive(PartialEq,Debug)]
 pub enum ASTNode {
-    CreateStatement { table_name: String, columns_to_add: Vec<Column>, next: Option<Box<ASTNode>>},
-    DropStatement { table_name: String, next: Option<Box<ASTNode>> },
-    SelectStatement { table_name: String, distinct: bool, columns: Vec<String>, next: Option<Box<ASTNode>>},
-    InsertStatement { table_name: String, columns: Vec<String>, values: Vec<String>, next: Option<Box<ASTNode>>},
+    CreateStatement { table_name: String, temporary: bool, if_not_exists: bool, columns_to_add: Vec<Column>, constraints: Vec<TableConstraint>, next: Option<Box<ASTNode>>},
+    DropStatement { table_name: String, if_exists: bool, cascade: bool, next: Option<Box<ASTNode>> },
+    // SELECT ... INTO table stores the result in a new table
+    SelectStatement { query: SelectQuery, into: Option<String>, next: Option<Box<ASTNode>>},
+    CreateTableAsStatement { table_name: String, temporary: bool, if_not_exists: bool, query: SelectQuery, with_data: bool, next: Option<Box<ASTNode>> },
+    InsertStatement { table_name: String, columns: Vec<String>, values: Vec<Expression>, next: Option<Box<ASTNode>>},
     ShowTablesStatement { next: Option<Box<ASTNode>> },
-    AlterTableStatement { table_name: String, columns_to_add: Vec<Column>, next: Option<Box<ASTNode>> },
-    TruncateTableStatement { table_name: String, next: Option<Box<ASTNode>> }
+    ShowCreateTableStatement { table_name: String, next: Option<Box<ASTNode>> },
+    DescribeStatement { table_name: String, next: Option<Box<ASTNode>> },
+    // CREATE DATABASE and DROP DATABASE work on schemas as well
+    CreateSchemaStatement { schema_name: String, if_not_exists: bool, next: Option<Box<ASTNode>> },
+    DropSchemaStatement { schema_name: String, if_exists: bool, cascade: bool, next: Option<Box<ASTNode>> },
+    UseStatement { schema_name: String, next: Option<Box<ASTNode>> },
+    AlterTableStatement { table_name: String, action: AlterAction, next: Option<Box<ASTNode>> },
+    TruncateTableStatement { table_name: String, cascade: bool, next: Option<Box<ASTNode>> },
+    SetStatement { name: String, value: String, next: Option<Box<ASTNode>> },
+    CreateTypeStatement { type_name: String, labels: Vec<String>, next: Option<Box<ASTNode>> },
+    CreateSequenceStatement { sequence_name: String, if_not_exists: bool, start: Option<i64>, increment: i64, min: Option<i64>, max: Option<i64>, next: Option<Box<ASTNode>> },
+    DropSequenceStatement { sequence_name: String, if_exists: bool, next: Option<Box<ASTNode>> },
+    CreateDomainStatement { domain_name: String, data_type: DataType, size: u32, nullable: bool, checks: Vec<Expression>, next: Option<Box<ASTNode>> }
 }
 
 impl Parser {
@@ -41,7 +306,10 @@ impl Parser {
     
     fn accept_indentation(&mut self)
     {
-        if self.current() == &" ".to_string() || self.current() == &"\t".to_string() {
+        while self.current() == &" ".to_string() || self.current() == &"\t".to_string() {
+            if self.cursor == self.tokens.len()-1 {
+                break;
+            }
             self.next();
         }
     }
@@ -57,83 +325,1013 @@ impl Parser {
     }
 
     fn tokenizer(&mut self, input: String) -> Vec<String> {
+        let operators = ["<=", ">=", "<>", "!=", "->", "::"];
         let mut list = Vec::new();
         let mut word = String::new();
-        for character in input.chars() {
-            if character == '\n' {
+        let characters: Vec<char> = input.chars().collect();
+        let mut i = 0;
+        while i < characters.len() {
+            let character = characters[i];
+            if character == '\n' || character == '\r' {
                 if word.len() > 0 {
                     list.push(word.clone());
                     word.clear();
                 }
-                continue;
             }
-            else if character == ';' || character == ' ' || character == '\t' || character == '(' || character == ')' || character == ',' || character == '*' {
+            else if character == '\'' {
+                // Quoted strings are kept as a single token, quotes included
+                word.push(character);
+                i += 1;
+                while i < characters.len() {
+                    word.push(characters[i]);
+                    if characters[i] == '\'' {
+                        if i + 1 < characters.len() && characters[i + 1] == '\'' {
+                            word.push('\'');
+                            i += 1;
+                        } else {
+                            break;
+                        }
+                    }
+                    i += 1;
+                }
+            }
+            // The sign of an exponent belongs to the number, as in 1e-5
+            else if (character == '-' || character == '+') && (word.ends_with('e') || word.ends_with('E'))
+                && word[..word.len() - 1].chars().all(|digit| digit.is_ascii_digit() || digit == '.') && word.starts_with(|digit: char| digit.is_ascii_digit()) {
+                word.push(character);
+            }
+            else if character == ';' || character == ' ' || character == '\t' || character == '(' || character == ')' || character == ',' || character == '*'
+                || character == '+' || character == '-' || character == '/' || character == '%' || character == '=' || character == '<' || character == '>' || character == '!' || character == ':' || character == '[' || character == ']' {
                 if word.len() > 0 {
                     list.push(word.clone());
                     word.clear();
                 }
-                list.push(character.to_string());
+                let mut operator = character.to_string();
+                if i + 1 < characters.len() {
+                    let pair: String = [character, characters[i + 1]].iter().collect();
+                    if operators.contains(&pair.as_str()) {
+                        operator = pair;
+                        i += 1;
+                    }
+                    if operator == "->" && i + 1 < characters.len() && characters[i + 1] == '>' {
+                        operator.push('>');
+                        i += 1;
+                    }
+                }
+                list.push(operator);
             }
             else {
                 word.push(character);
             }
+            i += 1;
+        }
+        if word.len() > 0 {
+            list.push(word);
         }
         return list;
     }
 
-    fn parseNewColumn(&mut self) -> Option<Column> {
+    // Parses the constraints following a column's data type and returns
+    // whether the column accepts NULL. Columns are nullable unless declared
+    // NOT NULL.
+    fn parse_column_constraints(&mut self) -> bool {
+        let mut nullable = true;
+        loop {
+            if self.accept(&"NOT".to_string()) {
+                self.next();
+                self.accept_indentation();
+                self.expect(&"NULL".to_string());
+                self.next();
+                self.accept_indentation();
+                nullable = false;
+            } else if self.accept(&"NULL".to_string()) {
+                self.next();
+                self.accept_indentation();
+                nullable = true;
+            } else {
+                return nullable;
+            }
+        }
+    }
+
+    // Parses a data type and returns it with its declared size
+    fn parse_data_type(&mut self) -> (DataType, u32) {
+        let (mut data_type, size) = self.parse_base_data_type();
+        // INT[] and VARCHAR(20)[] are arrays of the type
+        while self.accept(&"[".to_string()) {
+            self.next();
+            self.accept_indentation();
+            self.expect(&"]".to_string());
+            self.next();
+            self.accept_indentation();
+            data_type = DataType::ARRAY(Box::new(data_type));
+        }
+        return (data_type, size);
+    }
+
+    fn parse_base_data_type(&mut self) -> (DataType, u32) {
+        let type_name = self.current().clone();
+        self.next();
+        self.accept_indentation();
+        match type_name.as_str() {
+            "INT" | "INTEGER" => {
+                return (DataType::INT, 0); // TODO: Set this to 4 bytes
+            },
+            "BIGINT" => {
+                return (DataType::BIGINT, 0);
+            },
+            "SMALLINT" => {
+                return (DataType::SMALLINT, 0);
+            },
+            "REAL" | "FLOAT" => {
+                return (DataType::DOUBLE, 0);
+            },
+            "DOUBLE" => {
+                if self.accept(&"PRECISION".to_string()) {
+                    self.next();
+                    self.accept_indentation();
+                }
+                return (DataType::DOUBLE, 0);
+            },
+            "BOOLEAN" | "BOOL" => {
+                return (DataType::BOOLEAN, 0);
+            },
+            "DECIMAL" | "NUMERIC" => {
+                let mut precision = 10;
+                let mut scale = 0;
+                if self.accept(&"(".to_string()) {
+                    self.next();
+                    self.accept_indentation();
+                    precision = self.current().parse().unwrap();
+                    self.next();
+                    self.accept_indentation();
+                    if self.accept(&",".to_string()) {
+                        self.next();
+                        self.accept_indentation();
+                        scale = self.current().parse().unwrap();
+                        self.next();
+                        self.accept_indentation();
+                    }
+                    self.expect(&")".to_string());
+                    self.next();
+                    self.accept_indentation();
+                }
+                if precision < 1 || precision > 38 || scale > precision {
+                    panic!("Invalid DECIMAL precision or scale");
+                }
+                return (DataType::DECIMAL(precision, scale), 0);
+            },
+            "DATE" => {
+                return (DataType::DATE, 0);
+            },
+            "TIME" => {
+                if self.accept(&"WITHOUT".to_string()) {
+                    self.parse_time_zone_clause();
+                }
+                return (DataType::TIME, 0);
+            },
+            "TIMESTAMP" => {
+                if self.accept(&"WITH".to_string()) {
+                    self.parse_time_zone_clause();
+                    return (DataType::TIMESTAMPTZ, 0);
+                }
+                if self.accept(&"WITHOUT".to_string()) {
+                    self.parse_time_zone_clause();
+                }
+                return (DataType::TIMESTAMP, 0);
+            },
+            "TIMESTAMPTZ" => {
+                return (DataType::TIMESTAMPTZ, 0);
+            },
+            "INTERVAL" => {
+                return (DataType::INTERVAL, 0);
+            },
+            "BLOB" => {
+                return (DataType::BLOB, 0);
+            },
+            "JSON" => {
+                return (DataType::JSON, 0);
+            },
+            "UUID" => {
+                return (DataType::UUID, 0);
+            },
+            "VARBINARY" => {
+                self.expect(&"(".to_string());
+                self.next();
+                let data_size: u32 = self.current().parse().unwrap();
+                self.next();
+                self.accept_indentation();
+                self.expect(&")".to_string());
+                self.next();
+                self.accept_indentation();
+                return (DataType::BLOB, data_size);
+            },
+            "VARCHAR" => {
+                // VARCHAR without a length has no limit, which is TEXT
+                if !self.accept(&"(".to_string()) {
+                    return (DataType::TEXT, 0);
+                }
+                self.next();
+                let data_size_string: String = self.current().clone(); 
+                let data_size: u32 = data_size_string.parse().unwrap();
+                self.next();
+                self.accept_indentation();
+                self.expect(&")".to_string());
+                self.next();
+                self.accept_indentation();
+                return (DataType::STRING, data_size);
+            },
+            "TEXT" => {
+                return (DataType::TEXT, 0);
+            },
+            "CHAR" | "CHARACTER" => {
+                // CHAR without a length holds a single character
+                if !self.accept(&"(".to_string()) {
+                    return (DataType::CHAR, 1);
+                }
+                self.next();
+                let data_size: u32 = self.current().parse().unwrap();
+                self.next();
+                self.accept_indentation();
+                self.expect(&")".to_string());
+                self.next();
+                self.accept_indentation();
+                return (DataType::CHAR, data_size);
+            },
+            // Anything else names a type created with CREATE TYPE or
+            // CREATE DOMAIN, which is looked up when the column is added
+            _ => {
+                if !type_name.chars().all(|character| character.is_alphanumeric() || character == '_') {
+                    panic!("Unexpected data type");
+                }
+                return (DataType::USERDEFINED(type_name), 0);
+            }
+        }
+    }
+
+    // Skips WITH TIME ZONE or WITHOUT TIME ZONE
+    fn parse_time_zone_clause(&mut self) {
+        self.next();
+        self.accept_indentation();
+        self.expect(&"TIME".to_string());
+        self.next();
+        self.accept_indentation();
+        self.expect(&"ZONE".to_string());
+        self.next();
+        self.accept_indentation();
+    }
+
+    // Parses a column definition. PRIMARY KEY and UNIQUE declared on the
+    // column are added to the table's constraints.
+    fn parseNewColumn(&mut self, constraints: &mut Vec<TableConstraint>) -> Option<Column> {
         let column_name = self.current().clone();
         self.next();
         self.accept_indentation();
         //println!("Parser: Column named '{}'", column_name);
-        if self.accept(&"INT".to_string()) {
+        let (data_type, data_size) = self.parse_data_type();
+        let mut nullable = true;
+        let mut default = None;
+        let mut constraint_name = None;
+        let mut identity = None;
+        loop {
+            if self.accept(&"CONSTRAINT".to_string()) {
+                self.next();
+                self.accept_indentation();
+                constraint_name = Some(self.current().clone());
+                self.next();
+                self.accept_indentation();
+            } else if self.accept(&"PRIMARY".to_string()) {
+                self.next();
+                self.accept_indentation();
+                self.expect(&"KEY".to_string());
+                self.next();
+                self.accept_indentation();
+                constraints.push(TableConstraint { name: constraint_name.take(), kind: ConstraintKind::PrimaryKey(vec![column_name.clone()]) });
+            } else if self.accept(&"UNIQUE".to_string()) {
+                self.next();
+                self.accept_indentation();
+                constraints.push(TableConstraint { name: constraint_name.take(), kind: ConstraintKind::Unique(vec![column_name.clone()]) });
+            } else if self.accept(&"REFERENCES".to_string()) {
+                let kind = self.parse_references(vec![column_name.clone()]);
+                constraints.push(TableConstraint { name: constraint_name.take(), kind: kind });
+            } else if self.accept(&"AUTO_INCREMENT".to_string()) {
+                self.next();
+                self.accept_indentation();
+                identity = Some(Identity::ByDefault);
+            } else if self.accept(&"GENERATED".to_string()) {
+                // GENERATED ALWAYS AS IDENTITY or GENERATED BY DEFAULT AS IDENTITY
+                self.next();
+                self.accept_indentation();
+                if self.accept(&"ALWAYS".to_string()) {
+                    identity = Some(Identity::Always);
+                } else {
+                    self.expect(&"BY".to_string());
+                    self.next();
+                    self.accept_indentation();
+                    self.expect(&"DEFAULT".to_string());
+                    identity = Some(Identity::ByDefault);
+                }
+                for keyword in ["AS", "IDENTITY"] {
+                    self.next();
+                    self.accept_indentation();
+                    self.expect(&keyword.to_string());
+                }
+                self.next();
+                self.accept_indentation();
+            } else if self.accept(&"CHECK".to_string()) {
+                let expression = self.parse_check();
+                constraints.push(TableConstraint { name: constraint_name.take(), kind: ConstraintKind::Check { expression: expression, column: Some(column_name.clone()) } });
+            } else if self.accept(&"DEFAULT".to_string()) {
+                self.next();
+                self.accept_indentation();
+                // Boolean operators would run into a following NOT NULL, so
+                // a default using them has to be parenthesized
+                default = Some(self.parse_additive());
+                self.accept_indentation();
+            } else if self.accept(&"NOT".to_string()) || self.accept(&"NULL".to_string()) {
+                nullable = self.parse_column_constraints();
+            } else {
+                break;
+            }
+        }
+        return Some(Column {
+            name: column_name,
+            data_type: data_type,
+            size: data_size,
+            nullable: nullable,
+            default: default,
+            identity: identity
+        });
+    }
+
+    // A parenthesized list of column names
+    fn parse_column_list(&mut self) -> Vec<String> {
+        self.expect(&"(".to_string());
+        self.next();
+        let mut columns = Vec::new();
+        while self.current() != &")".to_string() {
+            self.accept_indentation();
+            columns.push(self.current().clone());
             self.next();
             self.accept_indentation();
-            return Some(Column {
-                name: column_name,
-                data_type: DataType::INT,
-                size: 0, // TODO: Set this to 4 bytes
-                nullable: false // TODO: Set this to correct value
-            });
-        } else if self.accept(&"VARCHAR".to_string()) {
+            if self.accept(&",".to_string()) {
+                self.next();
+            }
+        }
+        self.next();
+        self.accept_indentation();
+        return columns;
+    }
+
+    fn is_table_constraint(&mut self) -> bool {
+        return self.accept(&"CONSTRAINT".to_string()) || self.accept(&"PRIMARY".to_string()) || self.accept(&"UNIQUE".to_string())
+            || self.accept(&"FOREIGN".to_string()) || self.accept(&"CHECK".to_string());
+    }
+
+    // CHECK (condition)
+    fn parse_check(&mut self) -> Expression {
+        self.expect(&"CHECK".to_string());
+        self.next();
+        self.accept_indentation();
+        self.expect(&"(".to_string());
+        self.next();
+        self.accept_indentation();
+        let expression = self.parse_expression();
+        self.accept_indentation();
+        self.expect(&")".to_string());
+        self.next();
+        self.accept_indentation();
+        return expression;
+    }
+
+    // REFERENCES table [(columns)] followed by ON DELETE action and
+    // ON UPDATE action in either order
+    fn parse_references(&mut self, columns: Vec<String>) -> ConstraintKind {
+        self.expect(&"REFERENCES".to_string());
+        self.next();
+        self.accept_indentation();
+        let table = self.current().clone();
+        self.next();
+        self.accept_indentation();
+        let mut references = Vec::new();
+        if self.accept(&"(".to_string()) {
+            references = self.parse_column_list();
+        }
+        let mut on_delete = ReferentialAction::NoAction;
+        let mut on_update = ReferentialAction::NoAction;
+        while self.accept(&"ON".to_string()) {
+            self.next();
+            self.accept_indentation();
+            let event = self.current().clone();
+            self.next();
+            self.accept_indentation();
+            let action = self.parse_referential_action();
+            match event.as_str() {
+                "DELETE" => on_delete = action,
+                "UPDATE" => on_update = action,
+                _ => panic!("Expected DELETE or UPDATE after ON but found {}", event)
+            }
+        }
+        return ConstraintKind::ForeignKey { columns: columns, table: table, references: references, on_delete: on_delete, on_update: on_update };
+    }
+
+    fn parse_referential_action(&mut self) -> ReferentialAction {
+        let word = self.current().clone();
+        self.next();
+        self.accept_indentation();
+        if word == "NO" || word == "SET" {
+            let second = self.current().clone();
             self.next();
             self.accept_indentation();
-            self.expect(&"(".to_string());
+            return match (word.as_str(), second.as_str()) {
+                ("NO", "ACTION") => ReferentialAction::NoAction,
+                ("SET", "NULL") => ReferentialAction::SetNull,
+                ("SET", "DEFAULT") => ReferentialAction::SetDefault,
+                _ => panic!("Unknown referential action {} {}", word, second)
+            };
+        }
+        match word.as_str() {
+            "RESTRICT" => ReferentialAction::Restrict,
+            "CASCADE" => ReferentialAction::Cascade,
+            _ => panic!("Unknown referential action {}", word)
+        }
+    }
+
+    // An optional IF EXISTS, or IF NOT EXISTS when creating
+    fn parse_if_exists(&mut self, not: bool) -> bool {
+        if !self.accept(&"IF".to_string()) {
+            return false;
+        }
+        self.next();
+        self.accept_indentation();
+        if not {
+            self.expect(&"NOT".to_string());
             self.next();
-            let data_size_string: String = self.current().clone(); 
-            let data_size: u32 = data_size_string.parse().unwrap();
+            self.accept_indentation();
+        }
+        self.expect(&"EXISTS".to_string());
+        self.next();
+        self.accept_indentation();
+        return true;
+    }
+
+    // An optional CASCADE or RESTRICT at the end of DROP and TRUNCATE
+    fn parse_drop_behavior(&mut self) -> bool {
+        if self.accept(&"CASCADE".to_string()) {
             self.next();
             self.accept_indentation();
-            self.expect(&")".to_string());
+            return true;
+        }
+        if self.accept(&"RESTRICT".to_string()) {
+            self.next();
+            self.accept_indentation();
+        }
+        return false;
+    }
+
+    // [CONSTRAINT name] followed by PRIMARY KEY (columns), UNIQUE (columns),
+    // FOREIGN KEY (columns) REFERENCES table [(columns)] or CHECK (condition)
+    fn parse_table_constraint(&mut self) -> TableConstraint {
+        let mut name = None;
+        if self.accept(&"CONSTRAINT".to_string()) {
+            self.next();
+            self.accept_indentation();
+            name = Some(self.current().clone());
+            self.next();
+            self.accept_indentation();
+        }
+        let kind = if self.accept(&"PRIMARY".to_string()) {
+            self.next();
+            self.accept_indentation();
+            self.expect(&"KEY".to_string());
+            self.next();
+            self.accept_indentation();
+            ConstraintKind::PrimaryKey(self.parse_column_list())
+        } else if self.accept(&"FOREIGN".to_string()) {
+            self.next();
+            self.accept_indentation();
+            self.expect(&"KEY".to_string());
             self.next();
             self.accept_indentation();
-            return Some(Column {
-                name: column_name,
-                data_type: DataType::STRING,
-                size: data_size, // TODO: Set this to 4 bytes
-                nullable: false // TODO: Set this to correct value
-            });
+            let columns = self.parse_column_list();
+            self.parse_references(columns)
+        } else if self.accept(&"CHECK".to_string()) {
+            ConstraintKind::Check { expression: self.parse_check(), column: None }
         } else {
-            panic!("Unexpected data type");
+            self.expect(&"UNIQUE".to_string());
+            self.next();
+            self.accept_indentation();
+            ConstraintKind::Unique(self.parse_column_list())
+        };
+        return TableConstraint { name: name, kind: kind };
+    }
+
+    fn parse_expression(&mut self) -> Expression {
+        return self.parse_or();
+    }
+
+    fn parse_or(&mut self) -> Expression {
+        let mut left = self.parse_and();
+        self.accept_indentation();
+        while self.accept(&"OR".to_string()) {
+            self.next();
+            self.accept_indentation();
+            let right = self.parse_and();
+            left = Expression::Binary { operator: "OR".to_string(), left: Box::new(left), right: Box::new(right) };
+            self.accept_indentation();
+        }
+        return left;
+    }
+
+    fn parse_and(&mut self) -> Expression {
+        let mut left = self.parse_not();
+        self.accept_indentation();
+        while self.accept(&"AND".to_string()) {
+            self.next();
+            self.accept_indentation();
+            let right = self.parse_not();
+            left = Expression::Binary { operator: "AND".to_string(), left: Box::new(left), right: Box::new(right) };
+            self.accept_indentation();
+        }
+        return left;
+    }
+
+    fn parse_not(&mut self) -> Expression {
+        if self.accept(&"NOT".to_string()) {
+            self.next();
+            self.accept_indentation();
+            let operand = self.parse_not();
+            return Expression::Unary { operator: "NOT".to_string(), operand: Box::new(operand) };
+        }
+        return self.parse_comparison();
+    }
+
+    fn parse_comparison(&mut self) -> Expression {
+        let comparisons = ["=", "<>", "!=", "<", "<=", ">", ">="];
+        let left = self.parse_additive();
+        self.accept_indentation();
+        if comparisons.contains(&self.current().as_str()) {
+            let mut operator = self.current().clone();
+            self.next();
+            self.accept_indentation();
+            // x = ANY (array) and x > ALL (array) compare with each element
+            let quantifier = self.current().to_uppercase();
+            if (quantifier == "ANY" || quantifier == "SOME" || quantifier == "ALL") && self.peek_next_word() == "(" {
+                operator = format!("{} {}", operator, if quantifier == "ALL" { "ALL" } else { "ANY" });
+                self.next();
+                self.accept_indentation();
+            }
+            let right = self.parse_additive();
+            return Expression::Binary { operator: operator, left: Box::new(left), right: Box::new(right) };
+        }
+        if self.accept(&"IS".to_string()) {
+            self.next();
+            self.accept_indentation();
+            let mut operator = "IS NULL".to_string();
+            if self.accept(&"NOT".to_string()) {
+                self.next();
+                self.accept_indentation();
+                operator = "IS NOT NULL".to_string();
+            }
+            self.expect(&"NULL".to_string());
+            self.next();
+            return Expression::Unary { operator: operator, operand: Box::new(left) };
+        }
+        return left;
+    }
+
+    fn parse_additive(&mut self) -> Expression {
+        let mut left = self.parse_multiplicative();
+        self.accept_indentation();
+        while self.accept(&"+".to_string()) || self.accept(&"-".to_string()) {
+            let operator = self.current().clone();
+            self.next();
+            self.accept_indentation();
+            let right = self.parse_multiplicative();
+            left = Expression::Binary { operator: operator, left: Box::new(left), right: Box::new(right) };
+            self.accept_indentation();
+        }
+        return left;
+    }
+
+    fn parse_multiplicative(&mut self) -> Expression {
+        let mut left = self.parse_unary();
+        self.accept_indentation();
+        while self.accept(&"*".to_string()) || self.accept(&"/".to_string()) || self.accept(&"%".to_string()) {
+            let operator = self.current().clone();
+            self.next();
+            self.accept_indentation();
+            let right = self.parse_unary();
+            left = Expression::Binary { operator: operator, left: Box::new(left), right: Box::new(right) };
+            self.accept_indentation();
+        }
+        return left;
+    }
+
+    fn parse_unary(&mut self) -> Expression {
+        if self.accept(&"-".to_string()) {
+            self.next();
+            self.accept_indentation();
+            let operand = self.parse_unary();
+            // Fold the sign into numeric literals so the most negative
+            // integers can be written without overflowing
+            if let Expression::Number(text) = &operand {
+                if !text.starts_with('-') {
+                    return Expression::Number(format!("-{}", text));
+                }
+            }
+            return Expression::Unary { operator: "-".to_string(), operand: Box::new(operand) };
+        }
+        return self.parse_postfix();
+    }
+
+    // Array subscripts, casts written as expression::type and chains of
+    // document -> key and document ->> key bind tighter than any other
+    // operator
+    fn parse_postfix(&mut self) -> Expression {
+        let mut expression = self.parse_primary();
+        loop {
+            let start = self.cursor;
+            self.accept_indentation();
+            if self.accept(&"[".to_string()) {
+                self.next();
+                self.accept_indentation();
+                let index = self.parse_expression();
+                self.accept_indentation();
+                self.expect(&"]".to_string());
+                self.next();
+                expression = Expression::Binary { operator: "[]".to_string(), left: Box::new(expression), right: Box::new(index) };
+                continue;
+            }
+            if self.accept(&"::".to_string()) {
+                self.next();
+                self.accept_indentation();
+                let (data_type, size) = self.parse_data_type();
+                expression = Expression::Cast { expression: Box::new(expression), data_type: data_type, size: size };
+                continue;
+            }
+            if !self.accept(&"->".to_string()) && !self.accept(&"->>".to_string()) {
+                self.cursor = start;
+                return expression;
+            }
+            let operator = self.current().clone();
+            self.next();
+            self.accept_indentation();
+            // A negative index counts from the end of an array
+            let key = if self.accept(&"-".to_string()) {
+                self.next();
+                self.accept_indentation();
+                match self.parse_primary() {
+                    Expression::Number(text) => Expression::Number(format!("-{}", text)),
+                    operand => Expression::Unary { operator: "-".to_string(), operand: Box::new(operand) }
+                }
+            } else {
+                self.parse_primary()
+            };
+            expression = Expression::Binary { operator: operator, left: Box::new(expression), right: Box::new(key) };
+        }
+    }
+
+    fn parse_primary(&mut self) -> Expression {
+        let token = self.current().clone();
+        self.next();
+        if token == "(" {
+            self.accept_indentation();
+            let expression = self.parse_expression();
+            self.accept_indentation();
+            self.expect(&")".to_string());
+            self.next();
+            return expression;
+        }
+        if token.starts_with('\'') {
+            let text = token[1..token.len()-1].replace("''", "'");
+            return Expression::Text(text);
+        }
+        // Hex literals such as X'DEADBEEF'
+        if token.starts_with("X'") || token.starts_with("x'") {
+            return Expression::TypedLiteral { data_type: DataType::BLOB, text: token[2..token.len()-1].to_string() };
+        }
+        if token.chars().next().unwrap().is_ascii_digit() {
+            return Expression::Number(token);
+        }
+        // Typed literals such as DATE '2024-01-31'
+        let data_type = match token.as_str() {
+            "DATE" => Some(DataType::DATE),
+            "TIME" => Some(DataType::TIME),
+            "TIMESTAMP" => Some(DataType::TIMESTAMP),
+            "TIMESTAMPTZ" => Some(DataType::TIMESTAMPTZ),
+            "INTERVAL" => Some(DataType::INTERVAL),
+            _ => None
+        };
+        if data_type.is_some() {
+            let start = self.cursor;
+            self.accept_indentation();
+            if self.current().starts_with('\'') {
+                let literal = self.current().clone();
+                self.next();
+                return Expression::TypedLiteral { data_type: data_type.unwrap(), text: literal[1..literal.len()-1].replace("''", "'") };
+            }
+            self.cursor = start;
+        }
+        if token == "CURRENT_DATE" || token == "CURRENT_TIMESTAMP" {
+            return Expression::Function { name: token, arguments: Vec::new() };
+        }
+        if token.to_uppercase() == "ARRAY" && self.accept(&"[".to_string()) {
+            // ARRAY[1, 2, 3] is passed on as a function building the array
+            self.next();
+            self.accept_indentation();
+            let mut elements = Vec::new();
+            while self.current() != &"]".to_string() {
+                elements.push(self.parse_expression());
+                self.accept_indentation();
+                if self.accept(&",".to_string()) {
+                    self.next();
+                    self.accept_indentation();
+                }
+            }
+            self.next();
+            return Expression::Function { name: "ARRAY".to_string(), arguments: elements };
+        }
+        if token.to_uppercase() == "CAST" && self.accept(&"(".to_string()) {
+            self.next();
+            self.accept_indentation();
+            let expression = self.parse_expression();
+            self.accept_indentation();
+            self.expect(&"AS".to_string());
+            self.next();
+            self.accept_indentation();
+            let (data_type, size) = self.parse_data_type();
+            self.expect(&")".to_string());
+            self.next();
+            return Expression::Cast { expression: Box::new(expression), data_type: data_type, size: size };
+        }
+        if token.to_uppercase() == "EXTRACT" && self.accept(&"(".to_string()) {
+            // EXTRACT(field FROM expression) is passed on as a function
+            // taking the field name and the value
+            self.next();
+            self.accept_indentation();
+            let field = self.current().to_uppercase();
+            self.next();
+            self.accept_indentation();
+            self.expect(&"FROM".to_string());
+            self.next();
+            self.accept_indentation();
+            let value = self.parse_expression();
+            self.accept_indentation();
+            self.expect(&")".to_string());
+            self.next();
+            return Expression::Function { name: "EXTRACT".to_string(), arguments: vec![Expression::Text(field), value] };
+        }
+        if self.accept(&"(".to_string()) {
+            self.next();
+            self.accept_indentation();
+            let mut arguments = Vec::new();
+            while self.current() != &")".to_string() {
+                arguments.push(self.parse_expression());
+                self.accept_indentation();
+                if self.accept(&",".to_string()) {
+                    self.next();
+                    self.accept_indentation();
+                }
+            }
+            self.next();
+            return Expression::Function { name: token.to_uppercase(), arguments: arguments };
+        }
+        match token.as_str() {
+            "NULL" => Expression::Null,
+            "DEFAULT" => Expression::Default,
+            "TRUE" => Expression::Boolean(true),
+            "FALSE" => Expression::Boolean(false),
+            _ => Expression::Column(token)
+        }
+    }
+
+    fn parse_select_item(&mut self) -> SelectItem {
+        if self.accept(&"*".to_string()) {
+            self.next();
+            return SelectItem { name: "*".to_string(), expression: Expression::Column("*".to_string()), aliased: false };
+        }
+        let start = self.cursor;
+        let expression = self.parse_expression();
+        let mut name = match &expression {
+            Expression::Column(name) => name.clone(),
+            _ => self.tokens[start..self.cursor].concat().trim().to_string()
+        };
+        let mut aliased = false;
+        self.accept_indentation();
+        if self.accept(&"AS".to_string()) {
+            self.next();
+            self.accept_indentation();
+            name = self.current().clone();
+            aliased = true;
+            self.next();
+        }
+        return SelectItem { name: name, expression: expression, aliased: aliased };
+    }
+
+    // Looks at the word following the current token without consuming it
+    fn peek_next_word(&mut self) -> String {
+        let start = self.cursor;
+        self.next();
+        self.accept_indentation();
+        let word = self.current().clone();
+        self.cursor = start;
+        return word;
+    }
+
+    fn parse_create_statement(&mut self) -> Option<Box<ASTNode>> {
+        match self.peek_next_word().as_str() {
+            "TYPE" => self.parse_create_type(),
+            "DOMAIN" => self.parse_create_domain(),
+            "SEQUENCE" => self.parse_create_sequence(),
+            "SCHEMA" | "DATABASE" => self.parse_create_schema(),
+            _ => self.parse_create_table()
+        }
+    }
+
+    // CREATE TYPE name AS ENUM ('label', ...)
+    fn parse_create_type(&mut self) -> Option<Box<ASTNode>> {
+        self.expect(&"CREATE".to_string());
+        self.next();
+        self.accept_indentation();
+        self.expect(&"TYPE".to_string());
+        self.next();
+        self.accept_indentation();
+        let type_name = self.current().clone();
+        self.next();
+        self.accept_indentation();
+        self.expect(&"AS".to_string());
+        self.next();
+        self.accept_indentation();
+        self.expect(&"ENUM".to_string());
+        self.next();
+        self.accept_indentation();
+        self.expect(&"(".to_string());
+        self.next();
+        self.accept_indentation();
+        let mut labels = Vec::new();
+        while self.current() != &")".to_string() {
+            let label = self.current().clone();
+            if !label.starts_with('\'') {
+                panic!("Expected a quoted enum label");
+            }
+            labels.push(label[1..label.len()-1].replace("''", "'"));
+            self.next();
+            self.accept_indentation();
+            if self.accept(&",".to_string()) {
+                self.next();
+                self.accept_indentation();
+            }
+        }
+        self.next();
+        self.accept_indentation();
+        self.expect(&";".to_string());
+        self.next();
+        println!("Parser: Create type named '{}'", type_name);
+        let next = self.parse();
+        return Some(Box::new(ASTNode::CreateTypeStatement { type_name: type_name, labels: labels, next: next }));
+    }
+
+    // CREATE SEQUENCE name followed by any of START [WITH] n, INCREMENT [BY] n,
+    // MINVALUE n and MAXVALUE n
+    fn parse_create_sequence(&mut self) -> Option<Box<ASTNode>> {
+        self.expect(&"CREATE".to_string());
+        self.next();
+        self.accept_indentation();
+        self.expect(&"SEQUENCE".to_string());
+        self.next();
+        self.accept_indentation();
+        let if_not_exists = self.parse_if_exists(true);
+        let sequence_name = self.current().clone();
+        self.next();
+        self.accept_indentation();
+        let mut start = None;
+        let mut increment = 1;
+        let mut min = None;
+        let mut max = None;
+        while self.current() != &";".to_string() {
+            let option = self.current().clone();
+            self.next();
+            self.accept_indentation();
+            if (option == "START" && self.accept(&"WITH".to_string())) || (option == "INCREMENT" && self.accept(&"BY".to_string())) {
+                self.next();
+                self.accept_indentation();
+            }
+            let value = self.parse_signed_integer();
+            match option.as_str() {
+                "START" => start = Some(value),
+                "INCREMENT" => increment = value,
+                "MINVALUE" => min = Some(value),
+                "MAXVALUE" => max = Some(value),
+                _ => panic!("Unknown sequence option {}", option)
+            }
+        }
+        self.expect(&";".to_string());
+        self.next();
+        println!("Parser: Create sequence named '{}'", sequence_name);
+        let next = self.parse();
+        return Some(Box::new(ASTNode::CreateSequenceStatement {
+            sequence_name: sequence_name,
+            if_not_exists: if_not_exists,
+            start: start,
+            increment: increment,
+            min: min,
+            max: max,
+            next: next
+        }));
+    }
+
+    fn parse_signed_integer(&mut self) -> i64 {
+        let mut sign = 1;
+        if self.accept(&"-".to_string()) {
+            sign = -1;
+            self.next();
+        }
+        let value: i64 = self.current().parse().expect("Expected an integer");
+        self.next();
+        self.accept_indentation();
+        return sign * value;
+    }
+
+    // CREATE DOMAIN name [AS] type [NOT NULL] [CHECK (condition)], where the
+    // condition refers to the value being checked as VALUE
+    fn parse_create_domain(&mut self) -> Option<Box<ASTNode>> {
+        self.expect(&"CREATE".to_string());
+        self.next();
+        self.accept_indentation();
+        self.expect(&"DOMAIN".to_string());
+        self.next();
+        self.accept_indentation();
+        let domain_name = self.current().clone();
+        self.next();
+        self.accept_indentation();
+        if self.accept(&"AS".to_string()) {
+            self.next();
+            self.accept_indentation();
+        }
+        let (data_type, size) = self.parse_data_type();
+        let mut nullable = true;
+        let mut checks = Vec::new();
+        loop {
+            if self.accept(&"CHECK".to_string()) {
+                self.next();
+                self.accept_indentation();
+                checks.push(self.parse_expression());
+                self.accept_indentation();
+            } else if self.accept(&"NOT".to_string()) || self.accept(&"NULL".to_string()) {
+                nullable = self.parse_column_constraints();
+            } else {
+                break;
+            }
         }
+        self.expect(&";".to_string());
+        self.next();
+        println!("Parser: Create domain named '{}'", domain_name);
+        let next = self.parse();
+        return Some(Box::new(ASTNode::CreateDomainStatement {
+            domain_name: domain_name,
+            data_type: data_type,
+            size: size,
+            nullable: nullable,
+            checks: checks,
+            next: next
+        }));
     }
 
     fn parse_create_table(&mut self) -> Option<Box<ASTNode>> {
         let mut columns_to_add = Vec::new();
+        let mut constraints = Vec::new();
         self.expect(&"CREATE".to_string());
         self.next();
         self.accept_indentation();
+        let temporary = self.accept(&"TEMPORARY".to_string()) || self.accept(&"TEMP".to_string());
+        if temporary {
+            self.next();
+            self.accept_indentation();
+        }
         self.expect(&"TABLE".to_string());
         self.next();
         self.accept_indentation();
+        let if_not_exists = self.parse_if_exists(true);
         let table_name = self.current().clone();
         self.next();
         self.accept_indentation();
+        if self.accept(&"AS".to_string()) {
+            return self.parse_create_table_as(table_name, temporary, if_not_exists);
+        }
         if self.accept(&"(".to_string()) {
             self.next();
             while self.current() != &")".to_string() {
                 self.accept_indentation();
-                let newColumn = self.parseNewColumn();
+                if self.is_table_constraint() {
+                    constraints.push(self.parse_table_constraint());
+                    if self.accept(&",".to_string()) {
+                        self.next();
+                        self.accept_indentation();
+                    }
+                    continue;
+                }
+                let newColumn = self.parseNewColumn(&mut constraints);
                 if newColumn.is_some() {
                     columns_to_add.push(newColumn.unwrap());
                     if self.accept(&",".to_string()) {
@@ -152,7 +1350,46 @@ impl Parser {
         let next = self.parse();
         return Some(Box::new(ASTNode::CreateStatement {
             table_name: table_name,
+            temporary: temporary,
+            if_not_exists: if_not_exists,
             columns_to_add: columns_to_add,
+            constraints: constraints,
+            next: next
+        }));
+    }
+
+    // CREATE TABLE name AS SELECT ... [WITH [NO] DATA]
+    fn parse_create_table_as(&mut self, table_name: String, temporary: bool, if_not_exists: bool) -> Option<Box<ASTNode>> {
+        self.expect(&"AS".to_string());
+        self.next();
+        self.accept_indentation();
+        let (query, into) = self.parse_select_query();
+        if into.is_some() {
+            panic!("SELECT ... INTO is not allowed in CREATE TABLE ... AS");
+        }
+        let mut with_data = true;
+        if self.accept(&"WITH".to_string()) {
+            self.next();
+            self.accept_indentation();
+            if self.accept(&"NO".to_string()) {
+                self.next();
+                self.accept_indentation();
+                with_data = false;
+            }
+            self.expect(&"DATA".to_string());
+            self.next();
+            self.accept_indentation();
+        }
+        self.expect(&";".to_string());
+        self.next();
+        println!("Parser: Create table named '{}'", table_name);
+        let next = self.parse();
+        return Some(Box::new(ASTNode::CreateTableAsStatement {
+            table_name: table_name,
+            temporary: temporary,
+            if_not_exists: if_not_exists,
+            query: query,
+            with_data: with_data,
             next: next
         }));
     }
@@ -167,6 +1404,19 @@ impl Parser {
         let table_name = self.current().clone();
         self.next();
         self.accept_indentation();
+        // INSERT INTO table DEFAULT VALUES fills every column with its default
+        if self.accept(&"DEFAULT".to_string()) {
+            self.next();
+            self.accept_indentation();
+            self.expect(&"VALUES".to_string());
+            self.next();
+            self.accept_indentation();
+            self.expect(&";".to_string());
+            self.next();
+            println!("Parser: Insert into table named '{}'", table_name);
+            let next = self.parse();
+            return Some(Box::new(ASTNode::InsertStatement { table_name: table_name, columns: Vec::new(), values: Vec::new(), next: next }));
+        }
         self.expect(&"(".to_string());
         self.next();
         let mut columns = Vec::new();
@@ -188,8 +1438,8 @@ impl Parser {
         self.next();
         let mut values = Vec::new();
         while self.current() != &")".to_string() {
-            values.push(self.current().clone());
-            self.next();
+            self.accept_indentation();
+            values.push(self.parse_expression());
             if self.accept(&",".to_string()) {
                 self.next();
             }
@@ -205,7 +1455,44 @@ impl Parser {
         return Some(Box::new(ASTNode::InsertStatement { table_name: table_name, columns: columns, values: values, next: next }));
     }
 
-    fn parse_select_statement(&mut self) -> Option<Box<ASTNode>> {
+    // A table name, or a table function call with an optional alias naming
+    // the function and its column: unnest(tags) AS t(tag)
+    fn parse_from_item(&mut self) -> FromItem {
+        let name = self.current().clone();
+        if self.peek_next_word() != "(" {
+            self.next();
+            self.accept_indentation();
+            return FromItem::Table(name);
+        }
+        let expression = self.parse_primary();
+        self.accept_indentation();
+        let mut alias = name.to_lowercase();
+        let mut column_name = alias.clone();
+        if self.accept(&"AS".to_string()) {
+            self.next();
+            self.accept_indentation();
+        }
+        if self.current() != &";".to_string() && self.current() != &"WITH".to_string() {
+            alias = self.current().clone();
+            column_name = alias.clone();
+            self.next();
+            if self.accept(&"(".to_string()) {
+                self.next();
+                self.accept_indentation();
+                column_name = self.current().clone();
+                self.next();
+                self.accept_indentation();
+                self.expect(&")".to_string());
+                self.next();
+            }
+            self.accept_indentation();
+        }
+        return FromItem::Function { expression: expression, alias: alias, column_name: column_name };
+    }
+
+    // Parses a SELECT up to the end of its FROM clause. The table named by
+    // an INTO clause is returned alongside the query.
+    fn parse_select_query(&mut self) -> (SelectQuery, Option<String>) {
         let mut distinct: bool = false;
         self.expect(&"SELECT".to_string());
         self.next();
@@ -216,31 +1503,124 @@ impl Parser {
             distinct = true;
         }
         let mut columns = Vec::new();
-        while self.current() != &"FROM".to_string() {
-            columns.push(self.current().clone());
-            self.next();
+        while self.current() != &"FROM".to_string() && self.current() != &"INTO".to_string() && self.current() != &";".to_string() {
+            columns.push(self.parse_select_item());
             self.accept_indentation();
             if self.accept(&",".to_string()) {
                 self.next();
                 self.accept_indentation();
             }
         }
-        self.expect(&"FROM".to_string());
+        let mut into = None;
+        if self.accept(&"INTO".to_string()) {
+            self.next();
+            self.accept_indentation();
+            into = Some(self.current().clone());
+            self.next();
+            self.accept_indentation();
+        }
+        let mut from = None;
+        if self.accept(&"FROM".to_string()) {
+            self.next();
+            self.accept_indentation();
+            from = Some(self.parse_from_item());
+        }
+        return (SelectQuery { from: from, distinct: distinct, columns: columns }, into);
+    }
+
+    fn parse_select_statement(&mut self) -> Option<Box<ASTNode>> {
+        let (query, into) = self.parse_select_query();
+        self.expect(&";".to_string());
+        self.next();
+        match &query.from {
+            Some(FromItem::Table(table_name)) => println!("Parser: Select from table named '{}'", table_name),
+            Some(FromItem::Function { alias, .. }) => println!("Parser: Select from function '{}'", alias),
+            None => println!("Parser: Select")
+        }
+        let next = self.parse();
+        return Some(Box::new(ASTNode::SelectStatement {
+            query: query,
+            into: into,
+            next: next
+        }));
+    }
+
+    // CREATE SCHEMA name or CREATE DATABASE name
+    fn parse_create_schema(&mut self) -> Option<Box<ASTNode>> {
+        self.expect(&"CREATE".to_string());
         self.next();
         self.accept_indentation();
-        let table_name = self.current().clone();
+        self.next();
+        self.accept_indentation();
+        let if_not_exists = self.parse_if_exists(true);
+        let schema_name = self.current().clone();
         self.next();
         self.accept_indentation();
         self.expect(&";".to_string());
         self.next();
-        println!("Parser: Select from table named '{}'", table_name);
+        println!("Parser: Create schema named '{}'", schema_name);
         let next = self.parse();
-        return Some(Box::new(ASTNode::SelectStatement {
-            table_name: table_name,
-            distinct: distinct,
-            columns: columns,
-            next: next
-        }));
+        return Some(Box::new(ASTNode::CreateSchemaStatement { schema_name: schema_name, if_not_exists: if_not_exists, next: next }));
+    }
+
+    fn parse_drop_statement(&mut self) -> Option<Box<ASTNode>> {
+        match self.peek_next_word().as_str() {
+            "SCHEMA" | "DATABASE" => self.parse_drop_schema(),
+            "SEQUENCE" => self.parse_drop_sequence(),
+            _ => self.parse_drop_table()
+        }
+    }
+
+    // DROP SCHEMA name or DROP DATABASE name
+    fn parse_drop_schema(&mut self) -> Option<Box<ASTNode>> {
+        self.expect(&"DROP".to_string());
+        self.next();
+        self.accept_indentation();
+        self.next();
+        self.accept_indentation();
+        let if_exists = self.parse_if_exists(false);
+        let schema_name = self.current().clone();
+        self.next();
+        self.accept_indentation();
+        let cascade = self.parse_drop_behavior();
+        self.expect(&";".to_string());
+        self.next();
+        println!("Parser: Drop schema named '{}'", schema_name);
+        let next = self.parse();
+        return Some(Box::new(ASTNode::DropSchemaStatement { schema_name: schema_name, if_exists: if_exists, cascade: cascade, next: next }));
+    }
+
+    // DROP SEQUENCE [IF EXISTS] name
+    fn parse_drop_sequence(&mut self) -> Option<Box<ASTNode>> {
+        self.expect(&"DROP".to_string());
+        self.next();
+        self.accept_indentation();
+        self.next();
+        self.accept_indentation();
+        let if_exists = self.parse_if_exists(false);
+        let sequence_name = self.current().clone();
+        self.next();
+        self.accept_indentation();
+        self.expect(&";".to_string());
+        self.next();
+        println!("Parser: Drop sequence named '{}'", sequence_name);
+        let next = self.parse();
+        return Some(Box::new(ASTNode::DropSequenceStatement { sequence_name: sequence_name, if_exists: if_exists, next: next }));
+    }
+
+    // USE name makes the schema the one unqualified table names are in
+    fn parse_use_statement(&mut self) -> Option<Box<ASTNode>> {
+        self.expect(&"USE".to_string());
+        self.next();
+        self.accept_indentation();
+        let schema_name = self.current().clone();
+        self.next();
+        self.accept_indentation();
+        self.expect(&";".to_string());
+        self.next();
+        println!("Parser: Use schema named '{}'", schema_name);
+        let next = self.parse();
+        return Some(Box::new(ASTNode::UseStatement { schema_name: schema_name, next: next }));
     }
 
     fn parse_drop_table(&mut self) -> Option<Box<ASTNode>> {
@@ -250,14 +1630,59 @@ impl Parser {
         self.expect(&"TABLE".to_string());
         self.next();
         self.accept_indentation();
+        let if_exists = self.parse_if_exists(false);
         let table_name = self.current().clone();
         self.next();
         self.accept_indentation();
+        let cascade = self.parse_drop_behavior();
         self.expect(&";".to_string());
         self.next();
         println!("Parser: Drop table named '{}'", table_name);
         let next = self.parse();
-        return Some(Box::new(ASTNode::DropStatement { table_name: table_name, next: next }));
+        return Some(Box::new(ASTNode::DropStatement { table_name: table_name, if_exists: if_exists, cascade: cascade, next: next }));
+    }
+
+    fn parse_show_statement(&mut self) -> Option<Box<ASTNode>> {
+        match self.peek_next_word().as_str() {
+            "CREATE" => self.parse_show_create_table(),
+            _ => self.parse_show_tables()
+        }
+    }
+
+    // SHOW CREATE TABLE name
+    fn parse_show_create_table(&mut self) -> Option<Box<ASTNode>> {
+        for keyword in ["SHOW", "CREATE", "TABLE"] {
+            self.expect(&keyword.to_string());
+            self.next();
+            self.accept_indentation();
+        }
+        let table_name = self.current().clone();
+        self.next();
+        self.accept_indentation();
+        self.expect(&";".to_string());
+        self.next();
+        println!("Parser: Show create table named '{}'", table_name);
+        let next = self.parse();
+        return Some(Box::new(ASTNode::ShowCreateTableStatement { table_name: table_name, next: next }));
+    }
+
+    // DESCRIBE name, also written DESCRIBE TABLE name
+    fn parse_describe_statement(&mut self) -> Option<Box<ASTNode>> {
+        self.expect(&"DESCRIBE".to_string());
+        self.next();
+        self.accept_indentation();
+        if self.accept(&"TABLE".to_string()) {
+            self.next();
+            self.accept_indentation();
+        }
+        let table_name = self.current().clone();
+        self.next();
+        self.accept_indentation();
+        self.expect(&";".to_string());
+        self.next();
+        println!("Parser: Describe table named '{}'", table_name);
+        let next = self.parse();
+        return Some(Box::new(ASTNode::DescribeStatement { table_name: table_name, next: next }));
     }
 
     fn parse_show_tables(&mut self) -> Option<Box<ASTNode>> {
@@ -275,7 +1700,6 @@ impl Parser {
     }
 
     fn parse_alter_statement(&mut self) -> Option<Box<ASTNode>> {
-        let mut columns = Vec::new();
         self.expect(&"ALTER".to_string());
         self.next();
         self.accept_indentation();
@@ -285,11 +1709,41 @@ impl Parser {
         let table_name = self.current().clone();
         self.next();
         self.accept_indentation();
+        let action = if self.accept(&"RENAME".to_string()) {
+            self.parse_alter_rename()
+        } else if self.accept(&"DROP".to_string()) {
+            self.parse_alter_drop()
+        } else if self.accept(&"ALTER".to_string()) {
+            self.parse_alter_column()
+        } else {
+            self.parse_alter_add()
+        };
+        self.expect(&";".to_string());
+        self.next();
+        println!("Parser: Alter table named '{}'", table_name);
+        let next = self.parse();
+        return Some(Box::new(ASTNode::AlterTableStatement {
+            table_name: table_name,
+            action: action,
+            next: next
+        }));
+    }
+
+    fn parse_alter_add(&mut self) -> AlterAction {
+        let mut columns = Vec::new();
+        let mut constraints = Vec::new();
         self.expect(&"ADD".to_string());
         self.next();
+        self.accept_indentation();
+        if self.is_table_constraint() {
+            return AlterAction::AddConstraint(self.parse_table_constraint());
+        }
+        if self.accept(&"COLUMN".to_string()) {
+            self.next();
+        }
         while self.current() != &";".to_string() {
             self.accept_indentation();
-            let newColumn = self.parseNewColumn();
+            let newColumn = self.parseNewColumn(&mut constraints);
             if newColumn.is_some() {
                 columns.push(newColumn.unwrap());
                 if self.accept(&",".to_string()) {
@@ -298,14 +1752,110 @@ impl Parser {
                 }
             }
         }
+        return AlterAction::AddColumns(columns, constraints);
+    }
+
+    fn parse_alter_drop(&mut self) -> AlterAction {
+        self.expect(&"DROP".to_string());
         self.next();
-        println!("Parser: Alter table named '{}'", table_name);
-        let next = self.parse();
-        return Some(Box::new(ASTNode::AlterTableStatement {
-            table_name: table_name,
-            columns_to_add: columns,
-            next: next
-        }));
+        self.accept_indentation();
+        if self.accept(&"COLUMN".to_string()) {
+            self.next();
+            self.accept_indentation();
+        }
+        let column = self.current().clone();
+        self.next();
+        self.accept_indentation();
+        return AlterAction::DropColumn(column);
+    }
+
+    // ALTER [COLUMN] c followed by [SET DATA] TYPE type [USING expression],
+    // SET NOT NULL, DROP NOT NULL, SET DEFAULT expression or DROP DEFAULT
+    fn parse_alter_column(&mut self) -> AlterAction {
+        self.expect(&"ALTER".to_string());
+        self.next();
+        self.accept_indentation();
+        if self.accept(&"COLUMN".to_string()) {
+            self.next();
+            self.accept_indentation();
+        }
+        let column = self.current().clone();
+        self.next();
+        self.accept_indentation();
+        let operation = self.current().clone();
+        self.next();
+        self.accept_indentation();
+        if operation == "TYPE" || (operation == "SET" && self.accept(&"DATA".to_string())) {
+            if operation == "SET" {
+                self.next();
+                self.accept_indentation();
+                self.expect(&"TYPE".to_string());
+                self.next();
+                self.accept_indentation();
+            }
+            let (data_type, size) = self.parse_data_type();
+            let mut using = None;
+            if self.accept(&"USING".to_string()) {
+                self.next();
+                self.accept_indentation();
+                using = Some(self.parse_expression());
+                self.accept_indentation();
+            }
+            return AlterAction::AlterColumnType { column: column, data_type: data_type, size: size, using: using };
+        }
+        if self.accept(&"NOT".to_string()) {
+            self.next();
+            self.accept_indentation();
+            self.expect(&"NULL".to_string());
+            self.next();
+            self.accept_indentation();
+            return match operation.as_str() {
+                "SET" => AlterAction::SetNotNull(column),
+                "DROP" => AlterAction::DropNotNull(column),
+                _ => panic!("Expected SET or DROP")
+            };
+        }
+        self.expect(&"DEFAULT".to_string());
+        self.next();
+        self.accept_indentation();
+        match operation.as_str() {
+            "SET" => {
+                let default = self.parse_expression();
+                self.accept_indentation();
+                return AlterAction::SetDefault { column: column, default: default };
+            },
+            "DROP" => AlterAction::DropDefault(column),
+            _ => panic!("Expected SET or DROP")
+        }
+    }
+
+    // RENAME [COLUMN] a TO b renames a column, RENAME TO u renames the table
+    fn parse_alter_rename(&mut self) -> AlterAction {
+        self.expect(&"RENAME".to_string());
+        self.next();
+        self.accept_indentation();
+        if self.accept(&"TO".to_string()) {
+            self.next();
+            self.accept_indentation();
+            let new_name = self.current().clone();
+            self.next();
+            self.accept_indentation();
+            return AlterAction::RenameTable(new_name);
+        }
+        if self.accept(&"COLUMN".to_string()) {
+            self.next();
+            self.accept_indentation();
+        }
+        let from = self.current().clone();
+        self.next();
+        self.accept_indentation();
+        self.expect(&"TO".to_string());
+        self.next();
+        self.accept_indentation();
+        let to = self.current().clone();
+        self.next();
+        self.accept_indentation();
+        return AlterAction::RenameColumn { from: from, to: to };
     }
 
     fn parseTruncateTable(&mut self) -> Option<Box<ASTNode>> {
@@ -318,21 +1868,48 @@ impl Parser {
         let table_name = self.current().clone();
         self.next();
         self.accept_indentation();
+        let cascade = self.parse_drop_behavior();
         self.expect(&";".to_string());
         self.next();
         println!("Parser: Truncate table named '{}'", table_name);
         let next = self.parse();
-        return Some(Box::new(ASTNode::TruncateTableStatement { table_name: table_name, next: next }));
+        return Some(Box::new(ASTNode::TruncateTableStatement { table_name: table_name, cascade: cascade, next: next }));
+    }
+
+    // SET name = value, also written as SET name TO value
+    fn parse_set_statement(&mut self) -> Option<Box<ASTNode>> {
+        self.expect(&"SET".to_string());
+        self.next();
+        self.accept_indentation();
+        let name = self.current().to_uppercase();
+        self.next();
+        self.accept_indentation();
+        if self.accept(&"=".to_string()) || self.accept(&"TO".to_string()) {
+            self.next();
+            self.accept_indentation();
+        }
+        let value = self.current().to_uppercase();
+        self.next();
+        self.accept_indentation();
+        self.expect(&";".to_string());
+        self.next();
+        println!("Parser: Set '{}'", name);
+        let next = self.parse();
+        return Some(Box::new(ASTNode::SetStatement { name: name, value: value, next: next }));
     }
 
     fn parse(&mut self) -> Option<Box<ASTNode>> {
         if self.accept(&"CREATE".to_string())
         {
-            return self.parse_create_table();
+            return self.parse_create_statement();
         }
         else if self.accept(&"SHOW".to_string())
         {
-            return self.parse_show_tables();
+            return self.parse_show_statement();
+        }
+        else if self.accept(&"DESCRIBE".to_string())
+        {
+            return self.parse_describe_statement();
         }
         else if self.accept(&"SELECT".to_string())
         {
@@ -344,7 +1921,7 @@ impl Parser {
         }
         else if self.accept(&"DROP".to_string())
         {
-            return self.parse_drop_table();
+            return self.parse_drop_statement();
         }
         else if self.accept(&"ALTER".to_string())
         {
@@ -354,6 +1931,14 @@ impl Parser {
         {
             return self.parseTruncateTable();
         }
+        else if self.accept(&"SET".to_string())
+        {
+            return self.parse_set_statement();
+        }
+        else if self.accept(&"USE".to_string())
+        {
+            return self.parse_use_statement();
+        }
         return None;
     }
     
@@ -6,11 +6,39 @@ files=(
     "alter_statement/alter1.sql"
     "insert_statement/insert1.sql"
     "drop_statement/drop1.sql"
+    "drop_statement/drop_if_exists.sql"
     "select_statement/select1.sql"
     "select_statement/select_distinct.sql"
     "truncate_statement/truncate1.sql"
     "create_statement//create_with_columns.sql"
     "alter_statement/alter2.sql"
+    "insert_statement/insert_null.sql"
+    "alter_statement/alter_not_null.sql"
+    "insert_statement/insert_numeric_types.sql"
+    "select_statement/select_arithmetic.sql"
+    "insert_statement/insert_decimal.sql"
+    "select_statement/select_sum_avg.sql"
+    "insert_statement/insert_datetime.sql"
+    "insert_statement/insert_blob.sql"
+    "insert_statement/insert_json.sql"
+    "insert_statement/insert_strings.sql"
+    "select_statement/select_cast.sql"
+    "create_statement/create_type.sql"
+    "create_statement/create_unique.sql"
+    "create_statement/create_foreign_key.sql"
+    "create_statement/create_check.sql"
+    "create_statement/create_sequence.sql"
+    "create_statement/create_table_as.sql"
+    "create_statement/show_create_table.sql"
+    "create_statement/create_schema.sql"
+    "create_statement/create_temporary.sql"
+    "select_statement/select_array.sql"
+    "select_statement/information_schema.sql"
+    "insert_statement/insert_uuid.sql"
+    "insert_statement/insert_default.sql"
+    "alter_statement/alter_rename.sql"
+    "alter_statement/alter_column.sql"
+    "alter_statement/alter_add_column.sql"
 )
 
 # Loop through each file in the array
@@ -0,0 +1,18 @@
+CREATE TABLE users (id INT, name VARCHAR(10));
+CREATE TABLE logs (id INT, message VARCHAR(20));
+INSERT INTO users (id, name) VALUES (1, 'ann');
+INSERT INTO logs (id, message) VALUES (1, 'started');
+ALTER TABLE users ADD age INT;
+INSERT INTO users (id, name, age) VALUES (2, 'bob', 30);
+SELECT * FROM users;
+SELECT * FROM logs;
+INSERT INTO logs (id, message) VALUES (2, 'stopped');
+SELECT * FROM logs;
+ALTER TABLE users ADD city VARCHAR(10);
+SELECT id, age, city FROM users;
+SELECT SUM(age), AVG(age) FROM users;
+ALTER TABLE users DROP COLUMN age;
+SELECT * FROM users;
+ALTER TABLE users ALTER COLUMN city TYPE INT;
+INSERT INTO users (id, name, city) VALUES (3, 'cid', 7);
+SELECT * FROM users;
@@ -0,0 +1,26 @@
+CREATE TABLE orders (id INT, code VARCHAR(10), amount VARCHAR(10), note VARCHAR(20));
+INSERT INTO orders (id, code, amount, note) VALUES (1, '100', '12.50', 'first');
+INSERT INTO orders (id, code, amount) VALUES (2, '200', '7');
+ALTER TABLE orders DROP COLUMN note;
+ALTER TABLE orders DROP missing;
+SELECT * FROM orders;
+ALTER TABLE orders ALTER COLUMN amount TYPE DECIMAL(6,2);
+ALTER TABLE orders ALTER COLUMN code SET DATA TYPE INT;
+SELECT id, code + 1, amount * 2 FROM orders;
+INSERT INTO orders (id, code, amount) VALUES (3, 'x', '1');
+ALTER TABLE orders ALTER id TYPE BOOLEAN;
+ALTER TABLE orders ALTER COLUMN id TYPE VARCHAR(10) USING CAST(id AS VARCHAR(10));
+ALTER TABLE orders ALTER COLUMN amount TYPE INT USING amount * 100;
+ALTER TABLE orders ALTER COLUMN code TYPE SMALLINT USING code * 1000;
+SELECT * FROM orders;
+ALTER TABLE orders ADD status VARCHAR(10);
+ALTER TABLE orders ALTER COLUMN status SET NOT NULL;
+ALTER TABLE orders ALTER COLUMN status SET DEFAULT 'open';
+ALTER TABLE orders ALTER COLUMN code SET DEFAULT 'abc';
+INSERT INTO orders (id, code, amount) VALUES ('3', 3, 300);
+ALTER TABLE orders ALTER COLUMN id SET NOT NULL;
+INSERT INTO orders (code, amount) VALUES (4, 400);
+ALTER TABLE orders ALTER COLUMN id DROP NOT NULL;
+ALTER TABLE orders ALTER COLUMN status DROP DEFAULT;
+INSERT INTO orders (code, amount) VALUES (4, 400);
+SELECT * FROM orders;
@@ -0,0 +1,5 @@
+CREATE TABLE test (id INT);
+ALTER TABLE test ADD text VARCHAR(255) NOT NULL;
+INSERT INTO test (id, text) VALUES (1, 'hello1');
+ALTER TABLE test ADD value INT NOT NULL;
+SHOW TABLES;
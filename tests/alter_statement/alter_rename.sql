@@ -0,0 +1,16 @@
+CREATE TABLE users (id INT, name VARCHAR(20));
+CREATE TABLE accounts (id INT);
+INSERT INTO users (id, name) VALUES (1, 'ada');
+ALTER TABLE users RENAME COLUMN name TO full_name;
+ALTER TABLE users RENAME id TO user_id;
+ALTER TABLE users RENAME COLUMN missing TO other;
+ALTER TABLE users RENAME COLUMN user_id TO full_name;
+SELECT * FROM users;
+SELECT user_id, full_name FROM users;
+SELECT name FROM users;
+ALTER TABLE users RENAME TO accounts;
+ALTER TABLE users RENAME TO people;
+ALTER TABLE missing RENAME TO other;
+SELECT * FROM people;
+SELECT * FROM users;
+SHOW TABLES;
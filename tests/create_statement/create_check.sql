@@ -0,0 +1,25 @@
+CREATE TABLE products (id INT, price DECIMAL(6,2) CHECK (price > 0), discount DECIMAL(6,2), CHECK (discount < price));
+INSERT INTO products (id, price, discount) VALUES (1, 10, 2);
+INSERT INTO products (id, price, discount) VALUES (2, 0, 0);
+INSERT INTO products (id, price, discount) VALUES (3, 5, 6);
+INSERT INTO products (id, price) VALUES (4, 8);
+SELECT * FROM products;
+CREATE TABLE accounts (id INT, balance INT CONSTRAINT positive_balance CHECK (balance >= 0), owner VARCHAR(10) NOT NULL CHECK (LENGTH(owner) > 2));
+INSERT INTO accounts (id, balance, owner) VALUES (1, -5, 'ann');
+INSERT INTO accounts (id, balance, owner) VALUES (2, 5, 'al');
+INSERT INTO accounts (id, balance, owner) VALUES (3, 5, 'bob');
+ALTER TABLE accounts ADD CONSTRAINT small_id CHECK (id < 3);
+ALTER TABLE accounts ADD CHECK (id < 10);
+ALTER TABLE accounts ADD CHECK (id > 0);
+ALTER TABLE accounts ADD CHECK (missing > 0);
+ALTER TABLE accounts ADD CHECK (id + 1);
+ALTER TABLE accounts ADD CONSTRAINT positive_balance CHECK (balance > 0);
+INSERT INTO accounts (id, balance, owner) VALUES (10, 5, 'cid');
+INSERT INTO accounts (id, balance, owner) VALUES (0, 5, 'cid');
+ALTER TABLE accounts RENAME COLUMN balance TO amount;
+INSERT INTO accounts (id, amount, owner) VALUES (4, -1, 'dan');
+ALTER TABLE accounts DROP COLUMN amount;
+INSERT INTO accounts (id, owner) VALUES (5, 'eve');
+ALTER TABLE accounts ALTER COLUMN owner TYPE CHAR(2) USING 'x';
+ALTER TABLE accounts ADD score INT DEFAULT 7 CHECK (score > 10);
+SELECT * FROM accounts;
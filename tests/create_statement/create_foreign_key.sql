@@ -0,0 +1,62 @@
+CREATE TABLE authors (id INT PRIMARY KEY, name VARCHAR(10));
+CREATE TABLE books (id INT PRIMARY KEY, author_id INT REFERENCES authors ON DELETE RESTRICT, title VARCHAR(20));
+INSERT INTO authors (id, name) VALUES (1, 'ann');
+INSERT INTO authors (id, name) VALUES (2, 'bob');
+INSERT INTO books (id, author_id, title) VALUES (1, 1, 'first');
+INSERT INTO books (id, author_id, title) VALUES (2, '2', 'second');
+INSERT INTO books (id, author_id, title) VALUES (3, 3, 'third');
+INSERT INTO books (id, title) VALUES (4, 'anonymous');
+SELECT * FROM books;
+CREATE TABLE editions (book INT, year INT, PRIMARY KEY (book, year));
+CREATE TABLE prints (id INT, book INT, year INT, CONSTRAINT prints_edition_fkey FOREIGN KEY (year, book) REFERENCES editions (year, book) ON DELETE NO ACTION ON UPDATE RESTRICT);
+INSERT INTO editions (book, year) VALUES (1, 2020);
+INSERT INTO prints (id, book, year) VALUES (1, 1, 2020);
+INSERT INTO prints (id, book, year) VALUES (2, 1, 2021);
+INSERT INTO prints (id, book, year) VALUES (3, NULL, 2021);
+SELECT * FROM prints;
+CREATE TABLE reviews (id INT, title VARCHAR(20), FOREIGN KEY (title) REFERENCES books (title));
+CREATE TABLE notes (id INT, book INT REFERENCES missing (id));
+CREATE TABLE staff (id INT PRIMARY KEY, manager INT REFERENCES staff (id));
+INSERT INTO staff (id, manager) VALUES (1, 1);
+INSERT INTO staff (id, manager) VALUES (2, 1);
+INSERT INTO staff (id, manager) VALUES (3, 4);
+SELECT * FROM staff;
+CREATE TABLE loans (book INT, reader INT);
+INSERT INTO loans (book, reader) VALUES (1, 7);
+INSERT INTO loans (book, reader) VALUES (9, 7);
+ALTER TABLE loans ADD FOREIGN KEY (book) REFERENCES books (id);
+ALTER TABLE loans ADD reader_name VARCHAR(10) REFERENCES authors (name);
+TRUNCATE TABLE loans;
+ALTER TABLE loans ADD FOREIGN KEY (book) REFERENCES books (id);
+INSERT INTO loans (book, reader) VALUES (1, 7);
+ALTER TABLE authors DROP COLUMN id;
+ALTER TABLE authors RENAME COLUMN id TO author_id;
+ALTER TABLE authors RENAME TO writers;
+INSERT INTO books (id, author_id, title) VALUES (5, 3, 'fifth');
+INSERT INTO books (id, author_id, title) VALUES (5, 2, 'fifth');
+TRUNCATE TABLE writers;
+DROP TABLE writers;
+TRUNCATE TABLE writers CASCADE;
+SELECT * FROM books;
+SELECT * FROM loans;
+DROP TABLE writers CASCADE;
+INSERT INTO books (id, author_id, title) VALUES (6, 9, 'sixth');
+SELECT * FROM books;
+CREATE TABLE owners (id INT PRIMARY KEY);
+INSERT INTO owners (id) VALUES (2);
+CREATE TABLE mismatched (owner VARCHAR(5) REFERENCES owners);
+CREATE TABLE wide (owner BIGINT REFERENCES owners);
+INSERT INTO wide (owner) VALUES (5000000000);
+INSERT INTO wide (owner) VALUES (2);
+SELECT * FROM wide;
+CREATE TABLE cascading (owner INT REFERENCES owners ON DELETE CASCADE);
+ALTER TABLE wide ADD FOREIGN KEY (owner) REFERENCES owners ON UPDATE SET NULL;
+ALTER TABLE wide ADD FOREIGN KEY (owner) REFERENCES owners ON DELETE SET DEFAULT;
+ALTER TABLE wide ALTER COLUMN owner TYPE DECIMAL(5,2);
+INSERT INTO wide (owner) VALUES (2.00);
+ALTER TABLE wide ALTER COLUMN owner TYPE TEXT USING owner::TEXT;
+ALTER TABLE owners ALTER COLUMN id TYPE VARCHAR(5) USING id::VARCHAR(5);
+ALTER TABLE owners ALTER COLUMN id TYPE BIGINT;
+INSERT INTO wide (owner) VALUES (2);
+SELECT * FROM wide;
+DESCRIBE wide;
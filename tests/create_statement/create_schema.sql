@@ -0,0 +1,38 @@
+CREATE TABLE items (id INT PRIMARY KEY, name VARCHAR(10));
+INSERT INTO items (id, name) VALUES (1, 'public');
+CREATE SCHEMA fixtures;
+CREATE DATABASE other;
+CREATE SCHEMA fixtures;
+CREATE SCHEMA IF NOT EXISTS fixtures;
+CREATE TABLE fixtures.items (id INT PRIMARY KEY, name VARCHAR(10), CHECK (id > 0));
+INSERT INTO fixtures.items (id, name) VALUES (2, 'fixtures');
+SELECT * FROM items;
+SELECT * FROM fixtures.items;
+USE fixtures;
+SELECT * FROM items;
+SELECT * FROM public.items;
+CREATE TABLE orders (id INT GENERATED ALWAYS AS IDENTITY, item INT REFERENCES items, old_item INT REFERENCES public.items);
+INSERT INTO orders (item, old_item) VALUES (2, 1);
+INSERT INTO orders (item, old_item) VALUES (1, 1);
+SELECT * FROM orders;
+SHOW TABLES;
+SHOW CREATE TABLE orders;
+ALTER TABLE orders RENAME TO purchases;
+SHOW TABLES;
+SELECT * FROM information_schema.tables;
+SELECT * FROM information_schema.schemata;
+SELECT constraint_name, table_schema, table_name FROM information_schema.table_constraints;
+USE public;
+SHOW TABLES;
+SELECT * FROM fixtures.purchases;
+USE missing;
+CREATE TABLE missing.items (id INT);
+DROP SCHEMA fixtures;
+DROP TABLE items;
+DROP SCHEMA fixtures CASCADE;
+DROP TABLE items CASCADE;
+SELECT * FROM information_schema.tables;
+DROP DATABASE other;
+DROP SCHEMA IF EXISTS other;
+DROP SCHEMA public;
+USE other;
@@ -0,0 +1,56 @@
+CREATE SEQUENCE counter;
+SELECT nextval('counter'), nextval('counter');
+SELECT currval('counter');
+CREATE SEQUENCE countdown START WITH 10 INCREMENT BY -5 MINVALUE 0;
+CREATE SEQUENCE countdown START 10 INCREMENT -5 MINVALUE 0 MAXVALUE 10;
+SELECT nextval('countdown');
+SELECT nextval('countdown');
+SELECT nextval('countdown');
+SELECT nextval('countdown');
+CREATE SEQUENCE fresh;
+SELECT currval('fresh');
+SELECT setval('fresh', 41), nextval('fresh');
+SELECT setval('fresh', 100, FALSE), nextval('fresh');
+SELECT nextval('missing');
+CREATE SEQUENCE counter;
+CREATE TABLE orders (id INT AUTO_INCREMENT, item VARCHAR(10));
+INSERT INTO orders (item) VALUES ('apple');
+INSERT INTO orders (item) VALUES ('pear');
+INSERT INTO orders (id, item) VALUES (10, 'plum');
+INSERT INTO orders (id, item) VALUES (DEFAULT, 'fig');
+SELECT * FROM orders;
+TRUNCATE TABLE orders;
+INSERT INTO orders (item) VALUES ('kiwi');
+SELECT * FROM orders;
+SELECT currval('orders_id_seq');
+CREATE TABLE tickets (id BIGINT GENERATED ALWAYS AS IDENTITY, seat VARCHAR(5), code INT DEFAULT nextval('counter'));
+INSERT INTO tickets (seat) VALUES ('A1');
+INSERT INTO tickets (id, seat) VALUES (5, 'A2');
+INSERT INTO tickets DEFAULT VALUES;
+SELECT * FROM tickets;
+ALTER TABLE tickets ALTER COLUMN id DROP DEFAULT;
+CREATE TABLE labels (name VARCHAR(10) GENERATED BY DEFAULT AS IDENTITY);
+CREATE TABLE legacy (name VARCHAR(10));
+INSERT INTO legacy (name) VALUES ('a');
+INSERT INTO legacy (name) VALUES ('b');
+ALTER TABLE legacy ADD id SMALLINT GENERATED BY DEFAULT AS IDENTITY;
+INSERT INTO legacy (name) VALUES ('c');
+SELECT * FROM legacy;
+DROP TABLE legacy;
+SELECT nextval('legacy_id_seq');
+CREATE TABLE passes (id INT AUTO_INCREMENT);
+ALTER TABLE passes RENAME TO old_passes;
+CREATE TABLE passes (id INT AUTO_INCREMENT, note VARCHAR(10));
+INSERT INTO passes (note) VALUES ('new');
+INSERT INTO old_passes (id) VALUES (DEFAULT);
+SELECT * FROM passes;
+SELECT * FROM old_passes;
+SELECT currval('passes_id_seq1');
+CREATE SEQUENCE scratch START 10;
+DROP SEQUENCE scratch;
+SELECT nextval('scratch');
+DROP SEQUENCE scratch;
+DROP SEQUENCE IF EXISTS scratch;
+CREATE SEQUENCE scratch START 10;
+SELECT nextval('scratch');
+DROP SEQUENCE passes_id_seq1;
@@ -0,0 +1,37 @@
+CREATE TABLE people (id INT, name VARCHAR(5), salary DECIMAL(6,2));
+INSERT INTO people (id, name, salary) VALUES (1, 'ann', 10.5);
+INSERT INTO people (id, name, salary) VALUES (2, 'bob', 20.25);
+CREATE TABLE staff AS SELECT id, name AS first_name, salary * 2 AS doubled FROM people;
+SELECT * FROM staff;
+INSERT INTO staff (id, first_name, doubled) VALUES (3, 'carol', 1);
+INSERT INTO staff (id, first_name, doubled) VALUES (4, 'dorothy', 1);
+SELECT * FROM staff;
+SELECT * FROM people;
+CREATE TABLE empty_staff AS SELECT * FROM people WITH NO DATA;
+SELECT * FROM empty_staff;
+CREATE TABLE full_staff AS SELECT * FROM people WITH DATA;
+SELECT * FROM full_staff;
+SELECT DISTINCT name INTO names FROM people;
+SELECT * FROM names;
+SELECT 1 + 2 AS total, 'x' AS letter INTO constants;
+SELECT * FROM constants;
+CREATE TABLE staff AS SELECT id FROM people;
+CREATE TABLE IF NOT EXISTS staff AS SELECT id FROM people;
+SELECT id INTO staff FROM people;
+CREATE TABLE pairs AS SELECT id, id FROM people;
+CREATE TABLE copies AS SELECT id FROM missing;
+CREATE TABLE computed AS SELECT id + 1 AS next_id, salary * 2 AS doubled, salary / 4 AS quarter, LENGTH(name) AS letters, id > 1 AS later FROM people WITH NO DATA;
+DESCRIBE computed;
+CREATE TABLE nobody (id INT, price DECIMAL(6,2));
+CREATE TABLE prices AS SELECT id + 1 AS next_id, price * 2 AS doubled, -price AS negated FROM nobody;
+DESCRIBE prices;
+CREATE TABLE totals AS SELECT SUM(price) AS total, AVG(id) AS average FROM nobody;
+DESCRIBE totals;
+INSERT INTO nobody (id, price) VALUES (1, 9999.99);
+CREATE TABLE doubled_prices AS SELECT price * 2 AS doubled FROM nobody;
+DESCRIBE doubled_prices;
+INSERT INTO doubled_prices (doubled) VALUES (100.00);
+SELECT * FROM doubled_prices;
+CREATE TABLE unnamed AS SELECT id, salary * 2 FROM people;
+SELECT LENGTH(name) INTO unnamed FROM people;
+SELECT salary * 2, LENGTH(name) FROM people;
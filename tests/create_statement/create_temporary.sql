@@ -0,0 +1,25 @@
+CREATE TABLE staging (id INT PRIMARY KEY, name VARCHAR(10));
+INSERT INTO staging (id, name) VALUES (1, 'permanent');
+CREATE TEMPORARY TABLE staging (id INT GENERATED ALWAYS AS IDENTITY PRIMARY KEY, name VARCHAR(10));
+INSERT INTO staging (name) VALUES ('temporary');
+SELECT * FROM staging;
+SELECT * FROM public.staging;
+SELECT * FROM pg_temp.staging;
+SHOW TABLES;
+SHOW CREATE TABLE staging;
+CREATE TEMP TABLE copies AS SELECT name FROM public.staging;
+SELECT * FROM copies;
+CREATE TEMPORARY TABLE details (id INT REFERENCES staging, note TEXT);
+INSERT INTO details (id, note) VALUES (1, 'ok');
+CREATE TABLE links (id INT REFERENCES staging);
+CREATE TABLE links (id INT);
+ALTER TABLE links ADD CONSTRAINT links_staging_fkey FOREIGN KEY (id) REFERENCES public.staging;
+SELECT * FROM information_schema.tables;
+CREATE TEMPORARY TABLE public.other (id INT);
+CREATE TEMPORARY TABLE copies (id INT);
+CREATE TEMPORARY TABLE IF NOT EXISTS copies (id INT);
+DROP SCHEMA pg_temp;
+DROP TABLE staging CASCADE;
+SELECT * FROM staging;
+DROP TABLE staging CASCADE;
+SHOW TABLES;
@@ -0,0 +1,22 @@
+CREATE TYPE status AS ENUM ('new', 'active', 'closed');
+CREATE TYPE mood AS ENUM ('sad', 'ok', 'happy');
+CREATE TYPE status AS ENUM ('a');
+CREATE TYPE dup AS ENUM ('x', 'y', 'x');
+CREATE DOMAIN percentage AS INT NOT NULL CHECK (VALUE >= 0 AND VALUE <= 100);
+CREATE DOMAIN short_code VARCHAR(4) CHECK (VALUE <> 'NONE');
+CREATE TABLE tickets (id INT, state status, progress percentage);
+ALTER TABLE tickets ADD code short_code;
+ALTER TABLE tickets ADD other missing_type;
+INSERT INTO tickets (id, state, progress, code) VALUES (1, 'active', 50, 'AB');
+INSERT INTO tickets (id, state, progress, code) VALUES (2, 'closed', '100', 'XYZ');
+INSERT INTO tickets (id, state, progress) VALUES (3, 'new', 0);
+INSERT INTO tickets (id, state, progress) VALUES (4, 'archived', 10);
+INSERT INTO tickets (id, state, progress) VALUES (5, 'new', 101);
+INSERT INTO tickets (id, state) VALUES (6, 'new');
+INSERT INTO tickets (id, state, progress, code) VALUES (7, 'new', 1, 'NONE');
+INSERT INTO tickets (id, state, progress, code) VALUES (8, 'new', 1, 'TOOLONG');
+INSERT INTO tickets (id, state, progress) VALUES (9, 3, 1);
+SELECT * FROM tickets;
+SELECT id, state < 'closed', state = 'new', state > CAST('new' AS status), CAST(state AS VARCHAR(10)) FROM tickets;
+SELECT CAST('happy' AS mood) > CAST('sad' AS mood), CAST('ok' AS mood);
+SELECT state = CAST('ok' AS mood) FROM tickets;
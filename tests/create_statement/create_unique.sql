@@ -0,0 +1,41 @@
+CREATE TABLE users (id INT PRIMARY KEY, email VARCHAR(20) UNIQUE, name VARCHAR(10));
+INSERT INTO users (id, email, name) VALUES (1, 'a@x.org', 'ann');
+INSERT INTO users (id, email, name) VALUES (1, 'b@x.org', 'bob');
+INSERT INTO users (id, email, name) VALUES ('2', 'a@x.org', 'bob');
+INSERT INTO users (id, name) VALUES (2, 'bob');
+INSERT INTO users (id, name) VALUES (3, 'cid');
+INSERT INTO users (email, name) VALUES ('d@x.org', 'dan');
+SELECT * FROM users;
+CREATE TABLE members (team INT, member INT, role VARCHAR(10), CONSTRAINT members_key PRIMARY KEY (team, member), UNIQUE (team, role));
+INSERT INTO members (team, member, role) VALUES (1, 1, 'lead');
+INSERT INTO members (team, member, role) VALUES (1, 2, 'dev');
+INSERT INTO members (team, member, role) VALUES (2, 1, 'lead');
+INSERT INTO members (team, member, role) VALUES (1, 2, 'qa');
+INSERT INTO members (team, member, role) VALUES (1, 3, 'dev');
+SELECT * FROM members;
+CREATE TABLE codes (code VARCHAR(10), label VARCHAR(10));
+INSERT INTO codes (code, label) VALUES ('a', 'one');
+INSERT INTO codes (code, label) VALUES ('a', 'two');
+ALTER TABLE codes ADD CONSTRAINT codes_code_key UNIQUE (code);
+ALTER TABLE codes ADD PRIMARY KEY (label);
+ALTER TABLE codes ADD PRIMARY KEY (code);
+ALTER TABLE codes ALTER COLUMN label DROP NOT NULL;
+INSERT INTO codes (code, label) VALUES ('b', 'one');
+ALTER TABLE codes ADD rank INT UNIQUE;
+INSERT INTO codes (code, label, rank) VALUES ('c', 'three', 1);
+INSERT INTO codes (code, label, rank) VALUES ('d', 'four', 1);
+ALTER TABLE codes RENAME COLUMN label TO name;
+INSERT INTO codes (code, name) VALUES ('e', 'one');
+ALTER TABLE codes ALTER COLUMN rank TYPE VARCHAR(10) USING CAST(rank AS VARCHAR(10));
+ALTER TABLE codes ALTER COLUMN code TYPE CHAR(1);
+INSERT INTO codes (code, name, rank) VALUES ('f', 'five', '1');
+SELECT * FROM codes;
+TRUNCATE TABLE codes;
+INSERT INTO codes (code, name) VALUES ('a', 'one');
+SELECT * FROM codes;
+CREATE TABLE prices (amount DECIMAL(6,2) UNIQUE);
+INSERT INTO prices (amount) VALUES (1.01);
+INSERT INTO prices (amount) VALUES (1.04);
+ALTER TABLE prices ALTER COLUMN amount TYPE DECIMAL(6,1);
+INSERT INTO prices (amount) VALUES (1.01);
+SELECT * FROM prices;
@@ -0,0 +1,24 @@
+CREATE TABLE departments (id INT PRIMARY KEY, name VARCHAR(20) NOT NULL UNIQUE);
+CREATE TABLE employees (id INT GENERATED ALWAYS AS IDENTITY, name VARCHAR(30) NOT NULL DEFAULT 'nobody', code CHAR(3), salary DECIMAL(8,2) DEFAULT (10 * 2 + 1) CHECK (salary >= 0 AND salary < 100000), department INT REFERENCES departments ON DELETE RESTRICT, tags TEXT[], hired DATE DEFAULT DATE '2024-01-31', CONSTRAINT employees_pkey PRIMARY KEY (id), CHECK (NOT (name = '') OR code IS NULL));
+DESCRIBE employees;
+DESCRIBE TABLE departments;
+SHOW CREATE TABLE departments;
+SHOW CREATE TABLE employees;
+CREATE TABLE employees_copy (
+    id INT GENERATED ALWAYS AS IDENTITY,
+    name VARCHAR(30) DEFAULT 'nobody' NOT NULL,
+    code CHAR(3),
+    salary DECIMAL(8,2) DEFAULT ((10 * 2) + 1),
+    department INT,
+    tags TEXT[],
+    hired DATE DEFAULT DATE '2024-01-31',
+    CONSTRAINT employees_copy_pkey PRIMARY KEY (id),
+    CONSTRAINT employees_copy_department_fkey FOREIGN KEY (department) REFERENCES departments (id) ON DELETE RESTRICT,
+    CONSTRAINT employees_salary_check CHECK ((salary >= 0) AND (salary < 100000)),
+    CONSTRAINT employees_check CHECK ((NOT (name = '')) OR (code IS NULL))
+);
+SHOW CREATE TABLE employees_copy;
+ALTER TABLE departments ADD COLUMN budget INT DEFAULT -5;
+SHOW CREATE TABLE departments;
+DESCRIBE missing;
+SHOW CREATE TABLE missing;
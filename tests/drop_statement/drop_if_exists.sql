@@ -0,0 +1,24 @@
+CREATE TABLE users (id INT, name VARCHAR(10));
+INSERT INTO users (id, name) VALUES (1, 'ann');
+CREATE TABLE users (id INT);
+CREATE TABLE IF NOT EXISTS users (id INT, email VARCHAR(20));
+SELECT * FROM users;
+DROP TABLE IF EXISTS logs;
+DROP TABLE logs;
+TRUNCATE TABLE logs;
+INSERT INTO logs (id) VALUES (1);
+CREATE TABLE IF NOT EXISTS logs (id INT);
+INSERT INTO logs (id) VALUES (1);
+SELECT * FROM logs;
+DROP TABLE IF EXISTS logs;
+DROP TABLE IF EXISTS logs CASCADE;
+CREATE SEQUENCE IF NOT EXISTS ids;
+CREATE SEQUENCE IF NOT EXISTS ids START 5;
+SELECT nextval('ids');
+CREATE TABLE IF NOT EXISTS bad (x nosuchtype, y INT);
+SELECT * FROM bad;
+CREATE TABLE IF NOT EXISTS bad (x INT, y INT);
+SELECT * FROM bad;
+CREATE TABLE counters (id INT GENERATED ALWAYS AS IDENTITY, total INT CHECK (missing > 0));
+SELECT nextval('counters_id_seq');
+SHOW TABLES;
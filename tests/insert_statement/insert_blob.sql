@@ -0,0 +1,9 @@
+CREATE TABLE tokens (id INT, hash VARBINARY(4), payload BLOB);
+INSERT INTO tokens (id, hash, payload) VALUES (1, X'DEADBEEF', X'00ff10');
+INSERT INTO tokens (id, hash, payload) VALUES (2, x'0102', X'');
+INSERT INTO tokens (id, hash) VALUES (3, X'0102030405');
+INSERT INTO tokens (id, hash) VALUES (4, X'ABC');
+INSERT INTO tokens (id, hash) VALUES (5, 'DEADBEEF');
+SELECT * FROM tokens;
+SELECT id, LENGTH(hash), OCTET_LENGTH(payload), hash = X'deadbeef', hash < X'05' FROM tokens;
+SELECT LENGTH('héllo'), OCTET_LENGTH('héllo');
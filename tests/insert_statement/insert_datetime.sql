@@ -0,0 +1,22 @@
+CREATE TABLE events (id INT, day DATE, at TIME, created TIMESTAMP, logged TIMESTAMP WITH TIME ZONE, duration INTERVAL);
+INSERT INTO events (id, day, at, created, logged, duration) VALUES (1, '2024-02-29', '13:45:30.25', '2024-02-29T13:45:30', '2024-02-29 13:45:30+02:00', 'P1Y2M3DT4H5M6S');
+INSERT INTO events (id, day, at, created, logged, duration) VALUES (2, DATE '2023-12-31', TIME '00:00', TIMESTAMP '2023-12-31 23:59:59.5', TIMESTAMPTZ '2023-12-31 23:00:00Z', INTERVAL '2 days 03:00:00');
+INSERT INTO events (id, day) VALUES (3, '2023-02-29');
+INSERT INTO events (id, at) VALUES (4, '25:00');
+INSERT INTO events (id, logged) VALUES (5, '2024-01-01 10:00:00+99:00');
+INSERT INTO events (id, duration) VALUES (6, 'soon');
+SELECT * FROM events;
+SELECT id, day + 1, day - 60, day - DATE '2023-01-01', day + at FROM events;
+SELECT id, created + duration, logged + INTERVAL '1 month', logged - TIMESTAMPTZ '2024-01-01 00:00:00Z', at + INTERVAL '12 hours' FROM events;
+SELECT id, day < DATE '2024-01-01', logged > TIMESTAMPTZ '2024-02-29 12:00:00+01:00', duration > INTERVAL '1 year' FROM events;
+SELECT id, EXTRACT(YEAR FROM day), EXTRACT(DOW FROM day), EXTRACT(WEEK FROM day), EXTRACT(HOUR FROM logged), EXTRACT(SECOND FROM at), EXTRACT(TIMEZONE FROM logged) FROM events;
+SELECT id, DATE_TRUNC('month', created), DATE_TRUNC('week', day), DATE_TRUNC('hour', logged), EXTRACT(EPOCH FROM duration) FROM events;
+SELECT DATE '2024-01-31' + INTERVAL '1 month', INTERVAL '1 day' * 1.5, -INTERVAL 'PT90M', INTERVAL '1 year' / 4;
+SELECT NOW() > TIMESTAMPTZ '2020-01-01T00:00:00Z', CURRENT_DATE > DATE '2020-01-01';
+SELECT day + 1.5 FROM events;
+SELECT EXTRACT(DAY FROM at) FROM events;
+SELECT DATE '2024-01-01' + 9223372036854775807;
+SELECT DATE '2024-01-01' - -9223372036854775808;
+SELECT DATE '2024-01-01' + 2000000000 + INTERVAL '1 day';
+SELECT INTERVAL '2000000000 years 2000000000 years';
+SELECT INTERVAL '3000000000 days';
@@ -0,0 +1,15 @@
+CREATE TABLE prices (item VARCHAR(20), price DECIMAL(6,2), rate NUMERIC(5,4), whole DECIMAL);
+INSERT INTO prices (item, price, rate, whole) VALUES ('pen', 1.25, 0.0825, 10);
+INSERT INTO prices (item, price, rate, whole) VALUES ('book', 12.345, 0.5, 9.5);
+INSERT INTO prices (item, price, rate) VALUES ('bag', -12.344, 1);
+INSERT INTO prices (item, price) VALUES ('car', 10000);
+INSERT INTO prices (item, price) VALUES ('boat', 9999.995);
+INSERT INTO prices (item, rate) VALUES ('tax', 10);
+SELECT * FROM prices;
+SELECT item, price * 2, price + rate, price / 3, price > 12.3 FROM prices;
+SELECT 0.1 + 0.2, 0.1 + 0.2 = 0.3, 1.50 * 1.5, 123456789012345678901234567890 + 1;
+SELECT CAST(0.000000000000000000000000000000000000000000001 AS INT);
+INSERT INTO prices (item, price) VALUES ('dust', 0.000000000000000000000000000000000000000000001);
+SELECT CAST('0.000000000000000000000000000000000000000000001' AS DECIMAL(5,2)) AS tiny;
+SELECT CAST(CAST(0.125 AS DOUBLE) AS DECIMAL(5,2)) AS half, CAST(CAST(-2.5 AS DOUBLE) AS DECIMAL(3,0)) AS negative, CAST(1e-300 AS DECIMAL(5,2)) AS tiny;
+SELECT CAST(1e300 AS DECIMAL(5,2));
@@ -0,0 +1,17 @@
+CREATE TABLE items (id INT NOT NULL DEFAULT 0, name VARCHAR(10) DEFAULT 'new', price DECIMAL(6,2) DEFAULT 1.5 * 2, added TIMESTAMP DEFAULT now());
+INSERT INTO items (id) VALUES (1);
+INSERT INTO items (id, name, price) VALUES (2, DEFAULT, DEFAULT);
+INSERT INTO items (id, name) VALUES (DEFAULT, NULL);
+INSERT INTO items DEFAULT VALUES;
+SELECT id, name, price, added IS NOT NULL FROM items;
+SELECT DEFAULT;
+CREATE TABLE tags (label VARCHAR(10) DEFAULT 'x' NOT NULL, weight INT DEFAULT 'heavy');
+SELECT * FROM tags;
+ALTER TABLE items ADD stock INT DEFAULT 10;
+ALTER TABLE items ADD code UUID NOT NULL DEFAULT gen_random_uuid();
+ALTER TABLE items ADD note VARCHAR(10) NOT NULL;
+INSERT INTO items (id, stock) VALUES (5, 3);
+SELECT id, stock, code IS NOT NULL, LENGTH(CAST(code AS VARCHAR(36))) FROM items;
+ALTER TABLE items ALTER COLUMN stock SET DEFAULT 20;
+INSERT INTO items (id) VALUES (6);
+SELECT id, stock FROM items;
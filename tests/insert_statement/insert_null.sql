@@ -0,0 +1,6 @@
+CREATE TABLE test (id INT NOT NULL, text VARCHAR(255) NULL, note VARCHAR(20));
+INSERT INTO test (id, text, note) VALUES (1, 'hello1', NULL);
+INSERT INTO test (id) VALUES (2);
+INSERT INTO test (id, text) VALUES (NULL, 'hello3');
+INSERT INTO test (text) VALUES ('hello4');
+SELECT * FROM test;
@@ -0,0 +1,9 @@
+CREATE TABLE test (small SMALLINT, id INT, big BIGINT, ratio DOUBLE, flag BOOLEAN);
+INSERT INTO test (small, id, big, ratio, flag) VALUES (1, 2, 5000000000, 0.25, TRUE);
+INSERT INTO test (small, id, big, ratio, flag) VALUES (-32768, -2147483648, -9223372036854775808, 1.5e3, FALSE);
+INSERT INTO test (small) VALUES (32768);
+INSERT INTO test (id) VALUES (2147483648);
+INSERT INTO test (big) VALUES (9223372036854775808);
+INSERT INTO test (flag) VALUES (1);
+INSERT INTO test (id, ratio) VALUES (2.5, 7);
+SELECT * FROM test;
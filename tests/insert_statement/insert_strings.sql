@@ -0,0 +1,15 @@
+CREATE TABLE people (code CHAR(3), name VARCHAR(5), bio TEXT, flag CHAR);
+INSERT INTO people (code, name, bio, flag) VALUES ('ab', 'Renée', 'A long biography that no VARCHAR(255) column would be able to hold once it keeps on going and going', 'Y');
+INSERT INTO people (code, name) VALUES ('xyz', 'Zoë  ');
+INSERT INTO people (code, name) VALUES ('abcd', 'Ann');
+INSERT INTO people (code, name) VALUES ('abc', 'Anneliese');
+INSERT INTO people (code, name) VALUES ('ab ', 'Jo');
+SET STRICT_MODE = OFF;
+INSERT INTO people (code, name) VALUES ('pqrs', 'Anneliese');
+INSERT INTO people (code, name) VALUES ('çàé', 'ÅÄÖåäöÆ');
+SET STRICT_MODE TO ON;
+INSERT INTO people (code, name) VALUES ('uvw', 'Bartholomew');
+SET STRICT_MODE = MAYBE;
+SET SOMETHING = ON;
+SELECT * FROM people;
+SELECT code, code = 'ab', LENGTH(code), OCTET_LENGTH(code), LENGTH(name), LENGTH(bio) FROM people;
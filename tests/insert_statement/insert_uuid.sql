@@ -0,0 +1,16 @@
+CREATE TABLE services (id UUID NOT NULL, name VARCHAR(20));
+INSERT INTO services (id, name) VALUES ('550e8400-e29b-41d4-a716-446655440000', 'billing');
+INSERT INTO services (id, name) VALUES ('{6BA7B810-9DAD-11D1-80B4-00C04FD430C8}', 'auth');
+INSERT INTO services (id, name) VALUES ('6ba7b8119dad11d180b400c04fd430c8', 'search');
+INSERT INTO services (id, name) VALUES ('550e8400-e29b-41d4-a716-44665544000', 'short');
+INSERT INTO services (id, name) VALUES ('550e8400-e29b-41d4-a716-44665544000g', 'bad digit');
+INSERT INTO services (id, name) VALUES (42, 'number');
+INSERT INTO services (id, name) VALUES ('a-0eebc99-9c0b4ef8bb6d6bb9bd380a1-1', 'hyphens');
+SELECT name, id = '550E8400-E29B-41D4-A716-446655440000', id < '6ba7b811-9dad-11d1-80b4-00c04fd430c8', id IS NOT NULL FROM services;
+SELECT DISTINCT id FROM services;
+CREATE TABLE sessions (id UUID);
+INSERT INTO sessions (id) VALUES (gen_random_uuid());
+INSERT INTO sessions (id) VALUES (gen_random_uuid());
+SELECT id IS NOT NULL, LENGTH(id::VARCHAR(36)) FROM sessions;
+SELECT LENGTH(CAST(gen_random_uuid() AS VARCHAR(36))), gen_random_uuid() = gen_random_uuid();
+SELECT CAST('550e8400-e29b-41d4-a716-446655440000' AS UUID), '6ba7b810-9dad-11d1-80b4-00c04fd430c8'::UUID::VARCHAR(40);
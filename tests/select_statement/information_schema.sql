@@ -0,0 +1,16 @@
+CREATE TABLE departments (id INT PRIMARY KEY, name VARCHAR(20) NOT NULL UNIQUE);
+CREATE TABLE employees (id INT GENERATED ALWAYS AS IDENTITY, name CHAR(10) DEFAULT 'nobody', salary DECIMAL(8,2) CHECK (salary > 0), department INT REFERENCES departments, data VARBINARY(16), PRIMARY KEY (id, name));
+SELECT * FROM information_schema.tables;
+SELECT * FROM information_schema.columns;
+SELECT * FROM information_schema.table_constraints;
+SELECT * FROM information_schema.key_column_usage;
+SELECT DISTINCT table_name FROM information_schema.columns;
+SELECT table_name INTO table_list FROM information_schema.tables;
+SELECT * FROM table_list;
+ALTER TABLE departments ADD COLUMN budget INT;
+SELECT table_name, column_name, ordinal_position FROM information_schema.columns;
+INSERT INTO information_schema.tables (table_name) VALUES ('x');
+DROP TABLE information_schema.tables;
+TRUNCATE TABLE information_schema.columns;
+CREATE TABLE information_schema.extra (id INT);
+SELECT * FROM information_schema.views;
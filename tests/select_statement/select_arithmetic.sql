@@ -0,0 +1,16 @@
+CREATE TABLE test (id INT, big BIGINT, ratio REAL, flag BOOL);
+INSERT INTO test (id, big, ratio, flag) VALUES (7, 3000000000, 0.5, TRUE);
+INSERT INTO test (id, big, ratio, flag) VALUES (-3, 10, 2, NULL);
+SELECT id + 1, id * 2, id / 2, id % 2, -id FROM test;
+SELECT big * 2, id + big, ratio * id, id / ratio FROM test;
+SELECT id > 0, big >= id, ratio = 2, flag AND id > 0, flag OR id < 0, NOT flag, flag IS NULL FROM test;
+SELECT (1 + 2) * 3, 10 / 4.0, 9223372036854775807 - 1;
+SELECT 2147483647 + 1;
+SELECT id * 2147483647 FROM test;
+SELECT id / 0 FROM test;
+SELECT id = 'seven' FROM test;
+SELECT 1e-5, 2.5E+3, 2.5e3, 4e-1 - 1, id-1 FROM test;
+SELECT 1e400;
+SELECT -1e400;
+INSERT INTO test (id, ratio) VALUES (8, 1e400);
+SELECT 1e-400;
@@ -0,0 +1,22 @@
+CREATE TABLE posts (id INT, tags VARCHAR(10)[], scores INT[]);
+INSERT INTO posts (id, tags, scores) VALUES (1, ARRAY['rust', 'sql'], ARRAY[5, 3, 9]);
+INSERT INTO posts (id, tags, scores) VALUES (2, '{"db", "big data", NULL}', '{1,2}');
+INSERT INTO posts (id, tags, scores) VALUES (3, ARRAY[], ARRAY['7', NULL]);
+INSERT INTO posts (id, tags) VALUES (4, ARRAY['way too long tag']);
+INSERT INTO posts (id, scores) VALUES (5, ARRAY[1, 'x']);
+INSERT INTO posts (id, scores) VALUES (6, 42);
+SELECT * FROM posts;
+SELECT id, tags[1], tags[2], tags[5], scores[1] + scores[2], ARRAY_LENGTH(tags, 1), CARDINALITY(scores) FROM posts;
+SELECT id, 'sql' = ANY(tags), 3 = ANY(scores), 0 < ALL(scores), 5 > ALL(scores), 8 <> ALL(scores) FROM posts;
+SELECT ARRAY[1, 2] = ARRAY[1, 2], ARRAY[1, 2] < ARRAY[1, 3], ARRAY[1] < ARRAY[1, 0], 2 = ANY(ARRAY[]), 2 = ALL(ARRAY[]);
+SELECT * FROM unnest(ARRAY['red', 'green', 'blue']);
+SELECT tag, tag = 'green' FROM unnest(ARRAY['red', 'green']) AS t(tag);
+SELECT SUM(n), AVG(n) FROM unnest(ARRAY[1, 2, 3, 4]) n;
+SELECT CAST(ARRAY[1, 2] AS VARCHAR(20)), '{3,4}'::INT[], CAST('{1.5,2}' AS DECIMAL(3,1)[]);
+SELECT 5[1];
+SELECT ARRAY[1, 'a'];
+SELECT ARRAY[TRUE, 1];
+SELECT ARRAY[1, NULL, 2.50, 3000000000], ARRAY[1, 1.5e0], ARRAY[1, 2], ARRAY['a', NULL, 'b'];
+CREATE TABLE widened AS SELECT ARRAY[1, 2.5] AS mixed;
+DESCRIBE widened;
+SELECT * FROM widened;
@@ -0,0 +1,18 @@
+CREATE TABLE readings (id INT, amount DECIMAL(6,2), active BOOLEAN, taken DATE, label VARCHAR(10), raw BLOB);
+INSERT INTO readings (id, amount, active, taken, label, raw) VALUES ('5', ' 12.345 ', 'yes', '2024-02-29', 'first', '\x0aff');
+INSERT INTO readings (id, amount, active, taken, label, raw) VALUES (6, '7', 'off', DATE '2024-03-01', 'second', 'abc');
+INSERT INTO readings (id, amount) VALUES ('five', 1);
+INSERT INTO readings (id, active) VALUES (7, 'maybe');
+INSERT INTO readings (id, label) VALUES (8, 42);
+INSERT INTO readings (id, amount) VALUES ('99999999999', 1);
+SELECT * FROM readings;
+SELECT id = '5', id < '5.5', '6' = id, amount > '10', active = 'true', taken = '2024-02-29', raw = X'616263' FROM readings;
+SELECT id + '10', '2.5' * id, EXTRACT(YEAR FROM '2024-05-06 07:08:09'), DATE_TRUNC('month', '2024-05-06') FROM readings;
+SELECT CAST('42' AS INT), CAST(3.7 AS INT), CAST(amount AS VARCHAR(3)), id::VARCHAR(10), CAST(TRUE AS INT), 0::BOOLEAN FROM readings;
+SELECT '2024-01-15'::DATE + 1, CAST('1 day' AS INTERVAL), '12.5'::DECIMAL(4,1), '1e3'::DOUBLE, CAST(raw AS TEXT), CAST('ab' AS CHAR(4)) FROM readings;
+SELECT CAST(SUM(amount) AS INT), SUM(id)::DOUBLE FROM readings;
+SELECT CAST('abc' AS INT);
+SELECT CAST(70000 AS SMALLINT);
+SELECT CAST(DATE '2024-01-01' AS INT);
+SELECT id = 'x' FROM readings;
+SELECT CAST(1 AS VARCHAR), CAST(amount AS VARCHAR), 2.5::VARCHAR, CAST('a very long label value' AS VARCHAR) FROM readings;
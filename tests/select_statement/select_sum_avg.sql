@@ -0,0 +1,10 @@
+CREATE TABLE orders (id INT, quantity SMALLINT, total DECIMAL(10,2), weight DOUBLE);
+INSERT INTO orders (id, quantity, total, weight) VALUES (1, 3, 0.10, 1.5);
+INSERT INTO orders (id, quantity, total, weight) VALUES (2, 1, 0.20, 2.5);
+INSERT INTO orders (id, quantity, total, weight) VALUES (3, 2, 0.40, NULL);
+INSERT INTO orders (id, quantity, total) VALUES (4, NULL, NULL);
+SELECT SUM(total), AVG(total), SUM(quantity), AVG(quantity), SUM(weight), AVG(weight) FROM orders;
+SELECT sum(total) * 2, SUM(id) + 1, AVG(total * quantity) FROM orders;
+SELECT SUM(id) FROM orders;
+SELECT id, SUM(total) FROM orders;
+SELECT id + SUM(id) FROM orders;
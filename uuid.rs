@@ -0,0 +1,51 @@
+// Helpers for UUIDs stored as their 16 bytes
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Reads the canonical form 550e8400-e29b-41d4-a716-446655440000. Upper case
+// digits, surrounding braces and missing hyphens are accepted as well.
+pub fn parse_uuid(text: &str) -> Option<[u8; 16]> {
+    let text = text.trim();
+    let text = text.strip_prefix('{').and_then(|rest| rest.strip_suffix('}')).unwrap_or(text);
+    let characters: Vec<char> = text.chars().collect();
+    // Hyphens are either all at their canonical positions or left out
+    let hyphens = [8, 13, 18, 23];
+    let digits: Vec<char> = if characters.len() == 36 && hyphens.iter().all(|position| characters[*position] == '-') {
+        characters.iter().enumerate().filter(|(position, _)| !hyphens.contains(position)).map(|(_, character)| *character).collect()
+    } else {
+        characters
+    };
+    if digits.len() != 32 {
+        return None;
+    }
+    let mut bytes = [0u8; 16];
+    for i in 0..16 {
+        let high = digits[i * 2].to_digit(16)?;
+        let low = digits[i * 2 + 1].to_digit(16)?;
+        bytes[i] = (high * 16 + low) as u8;
+    }
+    return Some(bytes);
+}
+
+pub fn format_uuid(bytes: &[u8; 16]) -> String {
+    let hex: Vec<String> = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    return format!("{}-{}-{}-{}-{}", hex[0..4].concat(), hex[4..6].concat(), hex[6..8].concat(), hex[8..10].concat(), hex[10..16].concat());
+}
+
+// A version 4 UUID. The standard library has no random number generator,
+// so the bytes come from the randomly seeded hasher behind HashMap.
+pub fn random_uuid() -> [u8; 16] {
+    let mut bytes = [0u8; 16];
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_nanos()).unwrap_or(0);
+    for half in 0..2 {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(nanos);
+        hasher.write_usize(half);
+        bytes[half * 8..half * 8 + 8].copy_from_slice(&hasher.finish().to_le_bytes());
+    }
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    return bytes;
+}